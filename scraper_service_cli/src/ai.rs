@@ -15,6 +15,7 @@ impl AIAnalyzer {
         }
     }
 
+    #[tracing::instrument(name = "analyze", skip_all, fields(pages = pages.len()))]
     pub async fn analyze_pages(&self, pages: &[Page]) -> Result<String, Box<dyn std::error::Error>> {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -60,7 +61,13 @@ impl AIAnalyzer {
             .send()
             .await?;
 
+        let status = response.status();
         let result = response.json::<serde_json::Value>().await?;
+        if !status.is_success() {
+            tracing::warn!(%status, error = %result["error"]["message"], "OpenAI request failed");
+        } else {
+            tracing::info!(tokens = %result["usage"]["total_tokens"], "analysis finished");
+        }
         Ok(result["choices"][0]["message"]["content"]
             .as_str()
             .unwrap_or("Analiz yapılamadı")
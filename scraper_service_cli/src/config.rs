@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, COOKIE};
+use reqwest::{Client, Proxy};
+use serde::{Deserialize, Serialize};
+
+use crate::frontier::{default_priority_rules, PriorityRule, RuleScorer};
+use crate::normalize::UrlNormalizer;
+use crate::scope::CrawlScope;
+
+pub const DEFAULT_USER_AGENT: &str = "AxelteBot/0.1 (+https://github.com/axelte)";
+
+/// Başarısız isteklerin yeniden denenmesi. Bekleme süresi her denemede katlanarak artar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// İlk deneme dahil en fazla deneme sayısı
+    pub max_attempts: u32,
+    pub initial_interval_ms: u64,
+    pub max_interval_ms: u64,
+    /// Bir adres için denemelere harcanacak toplam süre
+    pub max_elapsed_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_interval_ms: 500,
+            max_interval_ms: 10_000,
+            max_elapsed_secs: 60,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn backoff(&self) -> backoff::ExponentialBackoff {
+        backoff::ExponentialBackoff {
+            initial_interval: Duration::from_millis(self.initial_interval_ms),
+            current_interval: Duration::from_millis(self.initial_interval_ms),
+            max_interval: Duration::from_millis(self.max_interval_ms),
+            max_elapsed_time: Some(Duration::from_secs(self.max_elapsed_secs)),
+            ..backoff::ExponentialBackoff::default()
+        }
+    }
+}
+
+/// Scraper'ın bütün ayarları. Kodda builder metotlarıyla kurulabilir, CLI'da TOML dosyasından
+/// (`--config`), API'de ise JSON olarak okunur; verilmeyen alanlar varsayılanları alır.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScraperConfig {
+    /// Kaydedilecek en fazla sayfa; tarama tam bu sayıda durur
+    pub max_pages: usize,
+    /// Taramanın toplam süresi (saniye); dolduğunda sürmekte olan istekler iptal edilir
+    pub max_duration_secs: Option<u64>,
+    /// İndirilen sayfa gövdelerinin toplam boyutu (bayt); aşıldığında yeni istek başlatılmaz,
+    /// okunmakta olan gövdeler de yarıda bırakılır
+    pub max_bytes: Option<u64>,
+    /// Tek bir sayfa gövdesinin en fazla boyutu (bayt); daha büyük yanıtlar okunmaz
+    pub max_page_bytes: u64,
+    /// Sırada bekleyebilecek en fazla adres; dolunca en düşük öncelikliler atılır
+    pub max_frontier: usize,
+    /// Aynı derinlikteki adreslerden hangilerinin önce taranacağı
+    pub priority: Vec<PriorityRule>,
+    pub concurrent_requests: usize,
+    /// Sayfa başına saklanacak en fazla metin uzunluğu (karakter)
+    pub max_content_length: usize,
+    pub request_timeout_secs: u64,
+    pub connect_timeout_secs: u64,
+    /// İzlenecek en fazla yönlendirme; yönlendirme zinciri rapora yazılır
+    pub max_redirects: usize,
+    pub user_agent: String,
+    /// Her istekle gönderilecek ek başlıklar
+    pub headers: BTreeMap<String, String>,
+    /// Her istekle `Cookie` başlığında gönderilecek çerezler
+    pub cookies: BTreeMap<String, String>,
+    /// HTTP(S) proxy adresi, ör. `http://127.0.0.1:8080`
+    pub proxy: Option<String>,
+    /// robots.txt'de Crawl-delay yoksa aynı host'a art arda istekler arasındaki bekleme
+    pub crawl_delay_ms: u64,
+    /// Bir host'a beklemeden art arda yapılabilecek istek sayısı (token bucket kapasitesi)
+    pub host_burst: u32,
+    /// Aynı host'a aynı anda yapılabilecek en fazla istek (`concurrent_requests`'ten bağımsız)
+    pub max_concurrent_per_host: usize,
+    /// 429/503 yanıtlarındaki `Retry-After` süresinin uyulacak en üst değeri
+    pub max_retry_after_secs: u64,
+    /// `false` ise robots.txt ve Crawl-delay yok sayılır; sadece sahibi olduğumuz siteler için
+    pub respect_robots: bool,
+    /// Taramayı sitemap'lerdeki adreslerle de başlat
+    pub use_sitemaps: bool,
+    /// Sadece sitemap'lerdeki adresleri tara, sayfalardaki bağlantıları izleme
+    pub sitemap_only: bool,
+    pub retry: RetryPolicy,
+    pub normalizer: UrlNormalizer,
+    pub scope: CrawlScope,
+}
+
+impl Default for ScraperConfig {
+    fn default() -> Self {
+        ScraperConfig {
+            max_pages: 100,
+            max_duration_secs: None,
+            max_bytes: None,
+            max_page_bytes: 5 * 1024 * 1024,
+            max_frontier: 10_000,
+            priority: default_priority_rules(),
+            concurrent_requests: 5,
+            max_content_length: 10_000,
+            request_timeout_secs: 30,
+            connect_timeout_secs: 10,
+            max_redirects: 10,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            headers: BTreeMap::new(),
+            cookies: BTreeMap::new(),
+            proxy: None,
+            crawl_delay_ms: 500,
+            host_burst: 1,
+            max_concurrent_per_host: 2,
+            max_retry_after_secs: 120,
+            respect_robots: true,
+            use_sitemaps: true,
+            sitemap_only: false,
+            retry: RetryPolicy::default(),
+            normalizer: UrlNormalizer::default(),
+            scope: CrawlScope::default(),
+        }
+    }
+}
+
+impl ScraperConfig {
+    /// TOML dosyasından okur; dosyada olmayan alanlar varsayılan değerlerini alır.
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| format!("{}: {}", path.as_ref().display(), e))?;
+        Ok(toml::from_str(&text)?)
+    }
+
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration_secs = Some(duration.as_secs());
+        self
+    }
+
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn max_page_bytes(mut self, max_page_bytes: u64) -> Self {
+        self.max_page_bytes = max_page_bytes;
+        self
+    }
+
+    pub fn max_frontier(mut self, max_frontier: usize) -> Self {
+        self.max_frontier = max_frontier;
+        self
+    }
+
+    pub fn priority(mut self, rules: Vec<PriorityRule>) -> Self {
+        self.priority = rules;
+        self
+    }
+
+    pub fn concurrent_requests(mut self, concurrent_requests: usize) -> Self {
+        self.concurrent_requests = concurrent_requests;
+        self
+    }
+
+    pub fn max_content_length(mut self, max_content_length: usize) -> Self {
+        self.max_content_length = max_content_length;
+        self
+    }
+
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// robots.txt gruplarının eşleştirileceği ve isteklerde gönderilecek User-Agent.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn cookie(mut self, name: &str, value: &str) -> Self {
+        self.cookies.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_string());
+        self
+    }
+
+    pub fn crawl_delay(mut self, delay: Duration) -> Self {
+        self.crawl_delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    pub fn host_burst(mut self, burst: u32) -> Self {
+        self.host_burst = burst;
+        self
+    }
+
+    pub fn max_concurrent_per_host(mut self, max: usize) -> Self {
+        self.max_concurrent_per_host = max;
+        self
+    }
+
+    pub fn max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after_secs = max.as_secs();
+        self
+    }
+
+    pub fn respect_robots(mut self, respect: bool) -> Self {
+        self.respect_robots = respect;
+        self
+    }
+
+    pub fn use_sitemaps(mut self, enabled: bool) -> Self {
+        self.use_sitemaps = enabled;
+        self
+    }
+
+    pub fn sitemap_only(mut self, enabled: bool) -> Self {
+        self.sitemap_only = enabled;
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn normalizer(mut self, normalizer: UrlNormalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    pub fn scope(mut self, scope: CrawlScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Ayarları istek göndermeden denetler; hatalı alanı anlatan bir mesaj döner.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_pages == 0 {
+            return Err("max_pages must be at least 1".to_string());
+        }
+        if self.max_page_bytes == 0 {
+            return Err("max_page_bytes must be at least 1".to_string());
+        }
+        if self.concurrent_requests == 0 {
+            return Err("concurrent_requests must be at least 1".to_string());
+        }
+        if self.host_burst == 0 {
+            return Err("host_burst must be at least 1".to_string());
+        }
+        if self.max_concurrent_per_host == 0 {
+            return Err("max_concurrent_per_host must be at least 1".to_string());
+        }
+        if self.retry.max_attempts == 0 {
+            return Err("retry.max_attempts must be at least 1".to_string());
+        }
+        self.default_headers()?;
+        if let Some(proxy) = &self.proxy {
+            Proxy::all(proxy).map_err(|e| format!("invalid proxy '{}': {}", proxy, e))?;
+        }
+        self.scope.compile()?;
+        RuleScorer::new(&self.priority)?;
+        Ok(())
+    }
+
+    fn default_headers(&self) -> Result<HeaderMap, String> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid header name '{}'", name))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| format!("invalid value for header '{}'", name))?;
+            headers.insert(name, value);
+        }
+        if !self.cookies.is_empty() {
+            let cookie = self
+                .cookies
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; ");
+            let value = HeaderValue::from_str(&cookie).map_err(|_| "invalid cookie value".to_string())?;
+            headers.insert(COOKIE, value);
+        }
+        Ok(headers)
+    }
+
+    /// Bu ayarlarla HTTP istemcisi kurar.
+    pub fn build_client(&self) -> Result<Client, Box<dyn std::error::Error>> {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(self.request_timeout_secs))
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            // Yönlendirmeler zinciri kaydedebilmek için Scraper tarafından izlenir
+            .redirect(reqwest::redirect::Policy::none())
+            .user_agent(&self.user_agent)
+            .default_headers(self.default_headers()?);
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        Ok(builder.build()?)
+    }
+}
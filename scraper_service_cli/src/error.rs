@@ -0,0 +1,112 @@
+use std::error::Error as StdError;
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Bir adresin indirilmesi sırasında oluşan hata. Sadece geçici sınıflar yeniden denenir.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FetchError {
+    /// Host adı çözülemedi
+    Dns { message: String },
+    /// Bağlantı kurulamadı (reddedildi, TLS hatası, ...)
+    Connect { message: String },
+    Timeout,
+    /// 4xx yanıtı
+    ClientError { status: u16 },
+    /// 5xx yanıtı
+    ServerError { status: u16 },
+    /// Adres ya da yanıt gövdesi çözülemedi
+    Parse { message: String },
+    /// `max_redirects`'ten fazla yönlendirme
+    TooManyRedirects,
+    /// Bağlantı kurulduktan sonraki diğer aktarım hataları (gövde yarıda kesildi, ...)
+    Request { message: String },
+}
+
+impl FetchError {
+    pub fn from_status(status: StatusCode) -> Self {
+        if status.is_server_error() {
+            FetchError::ServerError { status: status.as_u16() }
+        } else {
+            FetchError::ClientError { status: status.as_u16() }
+        }
+    }
+
+    /// Tekrar denemenin sonucu değiştirebileceği hatalar: zaman aşımı, bağlantı hataları,
+    /// 5xx ve 408/425/429. DNS ve diğer 4xx hataları kalıcı sayılır.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Connect { .. }
+            | FetchError::Timeout
+            | FetchError::ServerError { .. }
+            | FetchError::Request { .. } => true,
+            FetchError::ClientError { status } => matches!(status, 408 | 425 | 429),
+            FetchError::Dns { .. } | FetchError::Parse { .. } | FetchError::TooManyRedirects => false,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Dns { message } => write!(f, "DNS error: {}", message),
+            FetchError::Connect { message } => write!(f, "connection error: {}", message),
+            FetchError::Timeout => f.write_str("request timed out"),
+            FetchError::ClientError { status } | FetchError::ServerError { status } => {
+                write!(f, "HTTP {}", status)
+            }
+            FetchError::Parse { message } => write!(f, "parse error: {}", message),
+            FetchError::TooManyRedirects => f.write_str("too many redirects"),
+            FetchError::Request { message } => write!(f, "request error: {}", message),
+        }
+    }
+}
+
+impl StdError for FetchError {}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        // En içteki hata mesajı en açıklayıcı olanıdır ("failed to lookup address", "connection refused")
+        let mut source: &dyn StdError = &e;
+        while let Some(inner) = source.source() {
+            source = inner;
+        }
+        let message = source.to_string();
+
+        if e.is_timeout() {
+            FetchError::Timeout
+        } else if e.is_connect() {
+            let chain = error_chain(&e);
+            if chain.contains("dns error") || chain.contains("failed to lookup address") {
+                FetchError::Dns { message }
+            } else {
+                FetchError::Connect { message }
+            }
+        } else if e.is_decode() || e.is_builder() {
+            FetchError::Parse { message }
+        } else if let Some(status) = e.status() {
+            FetchError::from_status(status)
+        } else {
+            FetchError::Request { message }
+        }
+    }
+}
+
+impl From<url::ParseError> for FetchError {
+    fn from(e: url::ParseError) -> Self {
+        FetchError::Parse { message: e.to_string() }
+    }
+}
+
+fn error_chain(e: &dyn StdError) -> String {
+    let mut chain = e.to_string();
+    let mut source = e.source();
+    while let Some(inner) = source {
+        chain.push_str(": ");
+        chain.push_str(&inner.to_string());
+        source = inner.source();
+    }
+    chain.to_lowercase()
+}
@@ -0,0 +1,232 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::Arc;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::scope::compile_pattern;
+
+/// Sıradaki adreslerin önceliğini belirler; yüksek puan önce ziyaret edilir.
+/// Puan sadece aynı derinlikteki adresleri sıralar, tarama her zaman derinlik sırasıyla ilerler.
+pub trait UrlScorer: Send + Sync {
+    fn score(&self, url: &Url, depth: usize) -> i32;
+}
+
+/// Yolu (ve sorguyu) desene uyan adreslere `weight` kadar puan ekler.
+/// Desen sözdizimi `CrawlScope` ile aynıdır: glob ya da `re:` ile başlayan düzenli ifade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityRule {
+    pub pattern: String,
+    pub weight: i32,
+}
+
+/// Varsayılan kurallar: proje analizinde en çok işe yarayan sayfalar önce taranır.
+pub fn default_priority_rules() -> Vec<PriorityRule> {
+    [
+        (r"re:(?i)/(docs|documentation)(/|$)", 10),
+        (r"re:(?i)white-?paper", 10),
+        (r"re:(?i)tokenomics", 10),
+        (r"re:(?i)/(roadmap|team|about)(/|$)", 5),
+    ]
+    .iter()
+    .map(|(pattern, weight)| PriorityRule {
+        pattern: pattern.to_string(),
+        weight: *weight,
+    })
+    .collect()
+}
+
+/// `PriorityRule` listesinden derlenmiş puanlayıcı; eşleşen bütün kuralların ağırlıkları toplanır.
+pub struct RuleScorer {
+    rules: Vec<(Regex, i32)>,
+}
+
+impl RuleScorer {
+    pub fn new(rules: &[PriorityRule]) -> Result<Self, String> {
+        Ok(RuleScorer {
+            rules: rules
+                .iter()
+                .map(|r| {
+                    compile_pattern(&r.pattern)
+                        .map(|re| (re, r.weight))
+                        .map_err(|e| format!("invalid priority pattern '{}': {}", r.pattern, e))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl UrlScorer for RuleScorer {
+    fn score(&self, url: &Url, _depth: usize) -> i32 {
+        let target = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        self.rules
+            .iter()
+            .filter(|(re, _)| re.is_match(&target))
+            .map(|(_, weight)| weight)
+            .sum()
+    }
+}
+
+/// (derinlik, -puan, sıra, adres): küçük olan önce çıkar. Sıra numarası eşit puanlı
+/// adreslerin bulunma sırasını korur.
+type Key = (usize, Reverse<i32>, u64, String);
+
+/// Sınırlı öncelik kuyruğu. Aynı adres kuyruğa bir kez girer; kapasite dolunca en düşük
+/// öncelikli adres atılır.
+pub struct Frontier {
+    queue: BTreeSet<Key>,
+    /// Şu an kuyrukta olan ya da daha önce kuyruktan çıkmış adresler
+    seen: HashSet<String>,
+    capacity: usize,
+    next_seq: u64,
+    scorer: Arc<dyn UrlScorer>,
+}
+
+impl Frontier {
+    pub fn new(capacity: usize, scorer: Arc<dyn UrlScorer>) -> Self {
+        Frontier {
+            queue: BTreeSet::new(),
+            seen: HashSet::new(),
+            capacity: capacity.max(1),
+            next_seq: 0,
+            scorer,
+        }
+    }
+
+    /// Adresi kuyruğa ekler; daha önce eklenmişse ya da kuyruk daha öncelikli adreslerle
+    /// doluysa `false` döner.
+    pub fn push(&mut self, url: &Url, depth: usize) -> bool {
+        let key = url.as_str();
+        if self.seen.contains(key) {
+            return false;
+        }
+
+        let entry = (depth, Reverse(self.scorer.score(url, depth)), self.next_seq, key.to_string());
+        if self.queue.len() >= self.capacity {
+            match self.queue.last() {
+                Some(worst) if *worst > entry => {
+                    let (_, _, _, evicted) = self.queue.pop_last().expect("queue is not empty");
+                    // Atılan adres başka bir sayfadan yeniden bulunursa tekrar eklenebilir
+                    self.seen.remove(&evicted);
+                }
+                _ => return false,
+            }
+        }
+
+        self.next_seq += 1;
+        self.seen.insert(entry.3.clone());
+        self.queue.insert(entry);
+        true
+    }
+
+    /// En öncelikli adresi ve derinliğini çıkarır.
+    pub fn pop(&mut self) -> Option<(String, usize)> {
+        self.queue.pop_first().map(|(depth, _, _, url)| (url, depth))
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(path: &str) -> Url {
+        Url::parse(&format!("https://example.com{}", path)).unwrap()
+    }
+
+    fn frontier(capacity: usize) -> Frontier {
+        Frontier::new(capacity, Arc::new(RuleScorer::new(&default_priority_rules()).unwrap()))
+    }
+
+    fn drain(frontier: &mut Frontier) -> Vec<(String, usize)> {
+        std::iter::from_fn(|| frontier.pop())
+            .map(|(u, depth)| (Url::parse(&u).unwrap().path().to_string(), depth))
+            .collect()
+    }
+
+    #[test]
+    fn pops_by_depth_then_score_then_insertion_order() {
+        let mut frontier = frontier(100);
+        frontier.push(&url("/blog"), 1);
+        frontier.push(&url("/deep/docs/"), 2);
+        frontier.push(&url("/about"), 1);
+        frontier.push(&url("/news"), 1);
+        frontier.push(&url("/docs/intro"), 1);
+        frontier.push(&url("/"), 0);
+
+        assert_eq!(
+            drain(&mut frontier),
+            vec![
+                ("/".to_string(), 0),
+                ("/docs/intro".to_string(), 1),
+                ("/about".to_string(), 1),
+                ("/blog".to_string(), 1),
+                ("/news".to_string(), 1),
+                ("/deep/docs/".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_url_is_queued_once() {
+        let mut frontier = frontier(100);
+        assert!(frontier.push(&url("/a"), 1));
+        assert!(!frontier.push(&url("/a"), 2));
+        assert_eq!(frontier.len(), 1);
+
+        // Kuyruktan çıkmış adres de yeniden eklenmez
+        frontier.pop();
+        assert!(!frontier.push(&url("/a"), 1));
+        assert!(frontier.is_empty());
+    }
+
+    #[test]
+    fn full_frontier_evicts_lowest_priority() {
+        let mut frontier = frontier(2);
+        assert!(frontier.push(&url("/blog"), 1));
+        assert!(frontier.push(&url("/news"), 1));
+
+        // Aynı öncelikte ama daha geç bulunan adres kuyruğa giremez
+        assert!(!frontier.push(&url("/events"), 1));
+        // Daha derin adres de giremez
+        assert!(!frontier.push(&url("/docs/deep"), 2));
+        // Daha sığ ya da daha yüksek puanlı adres en kötüsünü (/news) atar
+        assert!(frontier.push(&url("/whitepaper"), 1));
+        assert_eq!(frontier.len(), 2);
+
+        // Atılan adres sonradan yeniden bulunursa tekrar eklenebilir
+        assert!(frontier.push(&url("/news"), 0));
+        assert_eq!(
+            drain(&mut frontier),
+            vec![("/news".to_string(), 0), ("/whitepaper".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn rule_scorer_sums_matching_weights() {
+        let scorer = RuleScorer::new(&default_priority_rules()).unwrap();
+        assert_eq!(scorer.score(&url("/docs/tokenomics"), 0), 20);
+        assert_eq!(scorer.score(&url("/Documentation"), 0), 10);
+        assert_eq!(scorer.score(&url("/team/"), 0), 5);
+        assert_eq!(scorer.score(&url("/teams"), 0), 0);
+        assert_eq!(scorer.score(&url("/download?file=White-Paper.pdf"), 0), 10);
+
+        let custom = RuleScorer::new(&[PriorityRule { pattern: "/blog/**".to_string(), weight: -5 }]).unwrap();
+        assert_eq!(custom.score(&url("/blog/2024/post"), 0), -5);
+
+        let invalid = [PriorityRule { pattern: "re:(".to_string(), weight: 1 }];
+        assert!(RuleScorer::new(&invalid).is_err());
+    }
+}
@@ -1,4 +1,13 @@
 pub mod scraper;
+pub mod config;
+pub mod error;
+pub mod frontier;
+pub mod normalize;
+pub mod politeness;
+pub mod report;
+pub mod robots;
+pub mod scope;
+pub mod sitemap;
 pub mod ai;
 pub mod utils;
 
@@ -1,8 +1,17 @@
 use clap::Parser;
-use scraper_service_cli::{ai::AIAnalyzer, scraper::Scraper, utils};
-use std::env;
+use scraper_service_cli::{
+    ai::AIAnalyzer,
+    config::ScraperConfig,
+    normalize::QueryHandling,
+    scope::SubdomainPolicy,
+    scraper::Scraper,
+};
+use std::{env, path::PathBuf};
 use dotenv::dotenv;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
 
+/// Flags override the values loaded from `--config`; flags that aren't given leave them as is.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -10,21 +19,203 @@ struct Args {
     #[arg(short = 'u', long)]
     url: String,
 
-    /// Maximum number of pages to scrape
-    #[arg(short = 'p', long, default_value_t = 100)]
-    max_pages: usize,
+    /// TOML file with scraper settings (see ScraperConfig)
+    #[arg(short = 'c', long)]
+    config: Option<PathBuf>,
 
-    /// Number of concurrent requests
-    #[arg(short = 'n', long, default_value_t = 5)]
-    concurrent: usize,
+    /// Maximum number of pages to scrape [default: 100]
+    #[arg(short = 'p', long)]
+    max_pages: Option<usize>,
 
-    /// Maximum content length per page (in characters)
-    #[arg(short = 'l', long, default_value_t = 10000)]
-    max_content_length: usize,
+    /// Stop crawling after this many seconds
+    #[arg(long)]
+    max_duration: Option<u64>,
+
+    /// Stop crawling after downloading this many bytes of page bodies
+    #[arg(long)]
+    max_bytes: Option<u64>,
+
+    /// Skip pages whose body is larger than this many bytes [default: 5242880]
+    #[arg(long)]
+    max_page_bytes: Option<u64>,
+
+    /// Number of concurrent requests [default: 5]
+    #[arg(short = 'n', long)]
+    concurrent: Option<usize>,
+
+    /// Minimum delay between requests to the same host in milliseconds, unless robots.txt sets Crawl-delay [default: 500]
+    #[arg(long)]
+    crawl_delay: Option<u64>,
+
+    /// Maximum concurrent requests to a single host [default: 2]
+    #[arg(long)]
+    per_host_concurrency: Option<usize>,
+
+    /// Attempts per URL, including the first, for transient errors (timeouts, 5xx, 429) [default: 3]
+    #[arg(long)]
+    max_attempts: Option<u32>,
+
+    /// Stop retrying a URL after this many seconds [default: 60]
+    #[arg(long)]
+    max_retry_time: Option<u64>,
+
+    /// Maximum content length per page (in characters) [default: 10000]
+    #[arg(short = 'l', long)]
+    max_content_length: Option<usize>,
 
     /// Skip AI analysis
     #[arg(short = 's', long)]
     skip_ai: bool,
+
+    /// Write logs to stderr as JSON lines (filter with RUST_LOG, default: info)
+    #[arg(long)]
+    log_json: bool,
+
+    /// User-Agent sent with requests and matched against robots.txt groups
+    #[arg(long)]
+    user_agent: Option<String>,
+
+    /// HTTP(S) proxy for all requests, e.g. http://127.0.0.1:8080
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// Ignore robots.txt and Crawl-delay (only for sites we own)
+    #[arg(long)]
+    ignore_robots: bool,
+
+    /// Don't seed the crawl from sitemap.xml
+    #[arg(long, conflicts_with = "sitemap_only")]
+    no_sitemaps: bool,
+
+    /// Crawl only the URLs listed in the site's sitemaps, without following links
+    #[arg(long)]
+    sitemap_only: bool,
+
+    /// How query strings are treated when deduplicating URLs: keep, sort or drop [default: sort]
+    #[arg(long)]
+    query: Option<QueryHandling>,
+
+    /// Keep tracking parameters (utm_*, fbclid, ...) in URLs
+    #[arg(long)]
+    keep_tracking_params: bool,
+
+    /// Maximum link depth from the start URL (start page is depth 0)
+    #[arg(long)]
+    max_depth: Option<usize>,
+
+    /// Only crawl paths matching this glob (or `re:<regex>`); repeatable
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Skip paths matching this glob (or `re:<regex>`); repeatable
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Only crawl paths starting with this prefix, e.g. /docs/
+    #[arg(long)]
+    path_prefix: Option<String>,
+
+    /// Which hosts are in scope: exact, same-site (ignores www.) or all subdomains [default: same-site]
+    #[arg(long)]
+    subdomains: Option<SubdomainPolicy>,
+
+    /// Comma-separated file extensions to skip (replaces the default list)
+    #[arg(long, value_delimiter = ',')]
+    skip_extensions: Option<Vec<String>>,
+}
+
+impl Args {
+    /// `--config` dosyasını (yoksa varsayılanları) okur ve verilen bayrakları üzerine yazar.
+    fn scraper_config(&self) -> Result<ScraperConfig, Box<dyn std::error::Error>> {
+        let mut config = match &self.config {
+            Some(path) => ScraperConfig::from_toml_file(path)?,
+            None => ScraperConfig::default(),
+        };
+
+        if let Some(max_pages) = self.max_pages {
+            config.max_pages = max_pages;
+        }
+        if self.max_duration.is_some() {
+            config.max_duration_secs = self.max_duration;
+        }
+        if self.max_bytes.is_some() {
+            config.max_bytes = self.max_bytes;
+        }
+        if let Some(max_page_bytes) = self.max_page_bytes {
+            config.max_page_bytes = max_page_bytes;
+        }
+        if let Some(concurrent) = self.concurrent {
+            config.concurrent_requests = concurrent;
+        }
+        if let Some(crawl_delay) = self.crawl_delay {
+            config.crawl_delay_ms = crawl_delay;
+        }
+        if let Some(per_host) = self.per_host_concurrency {
+            config.max_concurrent_per_host = per_host;
+        }
+        if let Some(max_attempts) = self.max_attempts {
+            config.retry.max_attempts = max_attempts;
+        }
+        if let Some(max_retry_time) = self.max_retry_time {
+            config.retry.max_elapsed_secs = max_retry_time;
+        }
+        if let Some(max_content_length) = self.max_content_length {
+            config.max_content_length = max_content_length;
+        }
+        if let Some(user_agent) = &self.user_agent {
+            config.user_agent = user_agent.clone();
+        }
+        if self.proxy.is_some() {
+            config.proxy = self.proxy.clone();
+        }
+        if self.ignore_robots {
+            config.respect_robots = false;
+        }
+        if self.no_sitemaps {
+            config.use_sitemaps = false;
+        }
+        if self.sitemap_only {
+            config.sitemap_only = true;
+        }
+        if let Some(query) = self.query {
+            config.normalizer.query = query;
+        }
+        if self.keep_tracking_params {
+            config.normalizer.strip_tracking_params = false;
+        }
+
+        let scope = &mut config.scope;
+        if self.max_depth.is_some() {
+            scope.max_depth = self.max_depth;
+        }
+        scope.include.extend(self.include.iter().cloned());
+        scope.exclude.extend(self.exclude.iter().cloned());
+        if self.path_prefix.is_some() {
+            scope.path_prefix = self.path_prefix.clone();
+        }
+        if let Some(subdomains) = self.subdomains {
+            scope.subdomains = subdomains;
+        }
+        if let Some(skip_extensions) = &self.skip_extensions {
+            scope.skip_extensions = skip_extensions.clone();
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Loglar stderr'e yazılır; stdout sadece JSON sonuç içindir.
+fn init_tracing(json: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    if json {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
 }
 
 #[tokio::main]
@@ -36,35 +227,38 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 2) Komut satırı argümanlarını parse et
     let args = Args::parse();
+    init_tracing(args.log_json);
 
     let t0 = std::time::Instant::now();
     // 3) Scraper'ı başlat ve sayfaları topla
-    let mut scraper = Scraper::new(&args.url, args.max_pages, args.concurrent, args.max_content_length)?;
-    let pages = scraper.scrape(&args.url).await?;
-    eprintln!("⏱️ Scraping took: {:?}", t0.elapsed());
-    if pages.is_empty() {
-        eprintln!("❌ Herhangi bir sayfa toplanamadı veya tüm sayfalar korumalıydı.");
-        return Ok(());
+    let scraper = Scraper::new(&args.url, args.scraper_config()?)?;
+    let report = scraper.scrape(&args.url).await?;
+    info!(elapsed = ?t0.elapsed(), "scraping finished");
+    if report.pages.is_empty() {
+        // Rapor yine de basılır; hangi adresin neden alınamadığı orada görülür
+        warn!("no pages could be scraped; see the report for per-URL outcomes");
     }
 
-    // 4) CLI çıktısını JSON olarak hazırlayın
-    let mut result = serde_json::Map::new();
-    result.insert("pages".to_string(), serde_json::to_value(&pages)?);
+    // 4) CLI çıktısını JSON olarak hazırlayın: sayfalar ve adres bazında tarama raporu
+    let mut result = match serde_json::to_value(&report)? {
+        serde_json::Value::Object(map) => map,
+        _ => unreachable!("CrawlReport serializes to an object"),
+    };
 
     let t1 = std::time::Instant::now();
     // 5) AI analizi yap (eğer atlanmadıysa) ve JSON’a ekle
-    if !args.skip_ai {
+    if !args.skip_ai && !report.pages.is_empty() {
         let analyzer = AIAnalyzer::new(api_key);
-        let analysis = analyzer.analyze_pages(&pages).await?;
+        let analysis = analyzer.analyze_pages(&report.pages).await?;
         result.insert("analysis".to_string(), serde_json::Value::String(analysis));
+        info!(elapsed = ?t1.elapsed(), "AI analysis finished");
     }
-    eprintln!("⏱️ AI analysis took: {:?}", t1.elapsed());
     let json_out = serde_json::Value::Object(result);
 
     // 6) Dosyalara yazmak isterseniz utils’i çağırın (opsiyonel)
-    // utils::save_json(&json_out, "result.json")?;
+    // scraper_service_cli::utils::save_json(&json_out, "result.json")?;
     // if let Some(serde_json::Value::String(ref txt)) = json_out.get("analysis") {
-    //     utils::save_text(txt, "analysis.txt")?;
+    //     scraper_service_cli::utils::save_text(txt, "analysis.txt")?;
     // }
 
     // 7) Mutlak JSON’u stdout’a basın
@@ -0,0 +1,217 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sorgu dizesinin normalizasyonu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryHandling {
+    /// Parametreleri olduğu gibi bırak (izleme parametreleri yine de atılır)
+    Keep,
+    /// Parametreleri ada göre sırala; `?a=1&b=2` ile `?b=2&a=1` aynı sayfa sayılır
+    Sort,
+    /// Sorgu dizesini tamamen at
+    Drop,
+}
+
+impl FromStr for QueryHandling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keep" => Ok(QueryHandling::Keep),
+            "sort" => Ok(QueryHandling::Sort),
+            "drop" => Ok(QueryHandling::Drop),
+            other => Err(format!("unknown query handling '{}' (expected keep, sort or drop)", other)),
+        }
+    }
+}
+
+impl fmt::Display for QueryHandling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            QueryHandling::Keep => "keep",
+            QueryHandling::Sort => "sort",
+            QueryHandling::Drop => "drop",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Aynı sayfanın farklı yazımlarını (`/page`, `/page/`, `/page#x`, `/page?utm_source=x`)
+/// tek bir adrese indirger. Şema/host küçük harfe çevrilir ve varsayılan port `url`
+/// tarafından zaten atılır; geri kalan kurallar buradan ayarlanır.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlNormalizer {
+    pub query: QueryHandling,
+    /// `utm_source`, `fbclid` gibi izleme parametrelerini at
+    pub strip_tracking_params: bool,
+    /// Atılacak parametreler; `*` ile biten girdiler önek olarak eşlenir
+    pub tracking_params: Vec<String>,
+    /// Kök dışındaki yollarda sondaki `/` işaretini at
+    pub strip_trailing_slash: bool,
+    /// Yolu küçük harfe çevir (büyük/küçük harf duyarsız sunucular için)
+    pub lowercase_path: bool,
+}
+
+impl Default for UrlNormalizer {
+    fn default() -> Self {
+        UrlNormalizer {
+            query: QueryHandling::Sort,
+            strip_tracking_params: true,
+            tracking_params: [
+                "utm_*", "fbclid", "gclid", "dclid", "msclkid", "yclid", "mc_cid", "mc_eid",
+                "_ga", "_gl", "igshid", "ref_src",
+            ]
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+            strip_trailing_slash: true,
+            lowercase_path: false,
+        }
+    }
+}
+
+impl UrlNormalizer {
+    pub fn normalize(&self, url: &Url) -> Url {
+        let mut url = url.clone();
+        url.set_fragment(None);
+
+        if self.lowercase_path {
+            let path = url.path().to_lowercase();
+            url.set_path(&path);
+        }
+        if self.strip_trailing_slash && url.path().len() > 1 && url.path().ends_with('/') {
+            let path = url.path().trim_end_matches('/').to_string();
+            url.set_path(if path.is_empty() { "/" } else { &path });
+        }
+
+        let mut pairs: Vec<(String, String)> = match self.query {
+            QueryHandling::Drop => Vec::new(),
+            _ => url
+                .query_pairs()
+                .filter(|(key, _)| !(self.strip_tracking_params && self.is_tracking(key)))
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect(),
+        };
+        if self.query == QueryHandling::Sort {
+            pairs.sort();
+        }
+
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else if self.query == QueryHandling::Keep && !self.strip_tracking_params {
+            // Hiçbir şey atılmadıysa orijinal kodlama korunur
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+        url
+    }
+
+    pub fn normalize_str(&self, url: &str) -> Option<String> {
+        Url::parse(url).ok().map(|u| self.normalize(&u).into())
+    }
+
+    fn is_tracking(&self, key: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        self.tracking_params.iter().any(|param| match param.strip_suffix('*') {
+            Some(prefix) => key.starts_with(prefix),
+            None => key == *param,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(normalizer: &UrlNormalizer, url: &str) -> String {
+        normalizer.normalize_str(url).unwrap()
+    }
+
+    #[test]
+    fn variants_of_the_same_page_collapse() {
+        let normalizer = UrlNormalizer::default();
+        for url in [
+            "https://example.com/page",
+            "https://example.com/page/",
+            "https://example.com/page#section",
+            "https://example.com/page?utm_source=x&utm_medium=y",
+            "https://example.com/page/?fbclid=abc#top",
+            "HTTPS://EXAMPLE.COM:443/page",
+        ] {
+            assert_eq!(normalize(&normalizer, url), "https://example.com/page", "{}", url);
+        }
+    }
+
+    #[test]
+    fn root_keeps_its_slash() {
+        let normalizer = UrlNormalizer::default();
+        assert_eq!(normalize(&normalizer, "https://example.com"), "https://example.com/");
+        assert_eq!(normalize(&normalizer, "https://example.com/#top"), "https://example.com/");
+        assert_eq!(normalize(&normalizer, "http://example.com:8080/a//"), "http://example.com:8080/a");
+    }
+
+    #[test]
+    fn www_and_apex_stay_distinct() {
+        // www/apex eşdeğerliği kapsamın işidir (SubdomainPolicy::SameSite); adres değişmez
+        let normalizer = UrlNormalizer::default();
+        assert_eq!(normalize(&normalizer, "https://www.example.com/a"), "https://www.example.com/a");
+        assert_ne!(
+            normalize(&normalizer, "https://www.example.com/a"),
+            normalize(&normalizer, "https://example.com/a")
+        );
+    }
+
+    #[test]
+    fn query_handling() {
+        let url = "https://example.com/list?b=2&utm_campaign=x&a=1";
+        let sort = UrlNormalizer::default();
+        assert_eq!(normalize(&sort, url), "https://example.com/list?a=1&b=2");
+        assert_eq!(
+            normalize(&sort, "https://example.com/list?a=1&b=2"),
+            normalize(&sort, "https://example.com/list?b=2&a=1")
+        );
+
+        let keep = UrlNormalizer { query: QueryHandling::Keep, ..UrlNormalizer::default() };
+        assert_eq!(normalize(&keep, url), "https://example.com/list?b=2&a=1");
+
+        let drop = UrlNormalizer { query: QueryHandling::Drop, ..UrlNormalizer::default() };
+        assert_eq!(normalize(&drop, url), "https://example.com/list");
+
+        // Hiçbir şey atılmıyorsa orijinal kodlama korunur
+        let raw = UrlNormalizer {
+            query: QueryHandling::Keep,
+            strip_tracking_params: false,
+            ..UrlNormalizer::default()
+        };
+        assert_eq!(
+            normalize(&raw, "https://example.com/s?q=a%20b&utm_source=x"),
+            "https://example.com/s?q=a%20b&utm_source=x"
+        );
+    }
+
+    #[test]
+    fn tracking_params_match_case_insensitively_and_by_prefix() {
+        let normalizer = UrlNormalizer::default();
+        assert_eq!(
+            normalize(&normalizer, "https://example.com/p?UTM_Source=x&utm_anything=y&id=3&gclid=z"),
+            "https://example.com/p?id=3"
+        );
+        // `utm_*` önek deseni, `utm` ile başlamayan benzer adları atmaz
+        assert_eq!(normalize(&normalizer, "https://example.com/p?xutm_source=1"), "https://example.com/p?xutm_source=1");
+    }
+
+    #[test]
+    fn optional_rules() {
+        let normalizer = UrlNormalizer {
+            strip_trailing_slash: false,
+            lowercase_path: true,
+            ..UrlNormalizer::default()
+        };
+        assert_eq!(normalize(&normalizer, "https://example.com/Docs/Intro/"), "https://example.com/docs/intro/");
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use url::Url;
+
+/// Sunucu yavaşlamamızı istedikçe (429/503) istekler arası sürenin çıkabileceği en üst değer
+const MAX_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Host başına token bucket ve eşzamanlılık sınırı. Her host `burst` kadar isteği art arda
+/// yapabilir, sonra her `interval`'da bir token kazanır. 429/503 yanıtlarında host
+/// `Retry-After` kadar (yoksa artan bir süre) bekletilir ve aralık iki katına çıkar;
+/// başarılı yanıtlarla aralık yavaşça başlangıç değerine iner.
+pub struct HostLimiter {
+    hosts: Mutex<HashMap<String, Arc<HostState>>>,
+    burst: u32,
+    max_concurrent: usize,
+    max_retry_after: Duration,
+}
+
+struct HostState {
+    bucket: Mutex<Bucket>,
+    permits: Arc<Semaphore>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// robots.txt Crawl-delay'i ya da yapılandırılmış gecikme
+    base_interval: Duration,
+    /// Sunucunun tepkisine göre uyarlanmış aralık (her zaman >= base_interval)
+    interval: Duration,
+    blocked_until: Option<Instant>,
+    /// Art arda alınan 429/503 sayısı
+    throttled: u32,
+}
+
+impl HostLimiter {
+    pub fn new(burst: u32, max_concurrent: usize, max_retry_after: Duration) -> Self {
+        HostLimiter {
+            hosts: Mutex::new(HashMap::new()),
+            burst: burst.max(1),
+            max_concurrent: max_concurrent.max(1),
+            max_retry_after,
+        }
+    }
+
+    async fn host(&self, url: &Url) -> Arc<HostState> {
+        let host = url.host_str().unwrap_or("").to_string();
+        self.hosts
+            .lock()
+            .await
+            .entry(host)
+            .or_insert_with(|| {
+                Arc::new(HostState {
+                    bucket: Mutex::new(Bucket {
+                        tokens: self.burst as f64,
+                        updated: Instant::now(),
+                        base_interval: Duration::ZERO,
+                        interval: Duration::ZERO,
+                        blocked_until: None,
+                        throttled: 0,
+                    }),
+                    permits: Arc::new(Semaphore::new(self.max_concurrent)),
+                })
+            })
+            .clone()
+    }
+
+    /// Host için bir eşzamanlılık hakkı ve bir token alınana kadar bekler. Dönen izin
+    /// yanıt gövdesi okunana kadar tutulmalıdır.
+    pub async fn acquire(&self, url: &Url, interval: Duration) -> OwnedSemaphorePermit {
+        let state = self.host(url).await;
+        let permit = state
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("host semaphore is never closed");
+
+        loop {
+            let wait = {
+                let mut bucket = state.bucket.lock().await;
+                let now = Instant::now();
+                if bucket.base_interval != interval {
+                    bucket.base_interval = interval;
+                    bucket.interval = bucket.interval.max(interval);
+                }
+                bucket.refill(now, self.burst);
+
+                match bucket.blocked_until.filter(|until| *until > now) {
+                    Some(until) => until - now,
+                    None if bucket.tokens >= 1.0 => {
+                        bucket.tokens -= 1.0;
+                        return permit;
+                    }
+                    None => bucket.interval.mul_f64(1.0 - bucket.tokens),
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// 429/503 alındı: host'u bekletir ve istek aralığını iki katına çıkarır.
+    pub async fn throttle(&self, url: &Url, retry_after: Option<Duration>) -> Duration {
+        let state = self.host(url).await;
+        let mut bucket = state.bucket.lock().await;
+        bucket.throttled += 1;
+        bucket.interval = (bucket.interval.max(Duration::from_millis(500)) * 2).min(MAX_INTERVAL);
+        bucket.tokens = 0.0;
+
+        // Retry-After yoksa her art arda gelen yanıtta bekleme iki katına çıkar
+        let backoff = bucket.interval * 2u32.saturating_pow(bucket.throttled - 1);
+        let wait = retry_after.unwrap_or(backoff).min(self.max_retry_after);
+        let until = Instant::now() + wait;
+        bucket.blocked_until = Some(bucket.blocked_until.map_or(until, |b| b.max(until)));
+        wait
+    }
+
+    /// Başarılı yanıt: aralık başlangıç değerine doğru %10 azalır.
+    pub async fn success(&self, url: &Url) {
+        let state = self.host(url).await;
+        let mut bucket = state.bucket.lock().await;
+        bucket.throttled = 0;
+        bucket.interval = bucket.interval.mul_f64(0.9).max(bucket.base_interval);
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant, burst: u32) {
+        let elapsed = now.duration_since(self.updated);
+        self.updated = now;
+        if self.interval.is_zero() {
+            self.tokens = burst as f64;
+        } else {
+            let earned = elapsed.as_secs_f64() / self.interval.as_secs_f64();
+            self.tokens = (self.tokens + earned).min(burst as f64);
+        }
+    }
+}
+
+/// `Retry-After` başlığı: saniye cinsinden süre ya da HTTP tarihi.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((date - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
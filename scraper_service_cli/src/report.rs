@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::FetchError;
+use crate::Page;
+
+/// Bir adresin taramadaki sonucu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UrlStatus {
+    /// İndirildi ve sayfa olarak kaydedildi
+    Fetched,
+    /// İndirildi ama aynı canonical adresli sayfa zaten kaydedilmişti
+    Duplicate { canonical: String },
+    /// Sayfa ya da bayt bütçesi dolduğu, veya gövde `max_page_bytes`'tan büyük olduğu için
+    /// kaydedilmedi
+    OverBudget,
+    SkippedByRobots,
+    /// Yönlendirme kapsam dışındaki bir adrese gitti; o adres indirilmedi
+    RedirectedOutOfScope { location: String },
+    /// Bot koruması (Cloudflare, "Checking your browser" sayfası)
+    Blocked { reason: String },
+    /// Bütün denemelerden sonra 2xx olmayan yanıt
+    HttpError { code: u16 },
+    /// Ağ, zaman aşımı ya da çözümleme hatası
+    Failed { error: FetchError },
+}
+
+impl UrlStatus {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, UrlStatus::HttpError { .. } | UrlStatus::Failed { .. })
+    }
+}
+
+impl From<FetchError> for UrlStatus {
+    fn from(error: FetchError) -> Self {
+        match error {
+            FetchError::ClientError { status } | FetchError::ServerError { status } => {
+                UrlStatus::HttpError { code: status }
+            }
+            error => UrlStatus::Failed { error },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UrlOutcome {
+    pub url: String,
+    pub depth: usize,
+    #[serde(flatten)]
+    pub status: UrlStatus,
+    /// Yönlendirmeyle geçilen adresler, sırasıyla (son adres dahil)
+    pub redirects: Vec<String>,
+    /// Yapılan istek sayısı (robots.txt ile atlananlarda 0)
+    pub attempts: u32,
+    /// Yeniden denemeler ve bekleme dahil toplam süre
+    pub elapsed_ms: u64,
+    /// İndirilen gövde boyutu
+    pub bytes: u64,
+}
+
+impl UrlOutcome {
+    pub fn new(url: String, depth: usize, status: UrlStatus) -> Self {
+        UrlOutcome {
+            url,
+            depth,
+            status,
+            redirects: Vec::new(),
+            attempts: 0,
+            elapsed_ms: 0,
+            bytes: 0,
+        }
+    }
+}
+
+/// Taramanın neden bittiği.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// Taranacak adres kalmadı
+    Completed,
+    MaxPages,
+    MaxDuration,
+    MaxBytes,
+}
+
+/// `Scraper::scrape` sonucu: kaydedilen sayfalar ve denenen her adresin sonucu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlReport {
+    pub start_url: String,
+    pub started_at: DateTime<Utc>,
+    pub elapsed_ms: u64,
+    pub bytes_downloaded: u64,
+    pub stop_reason: StopReason,
+    pub pages: Vec<Page>,
+    pub urls: Vec<UrlOutcome>,
+}
+
+impl CrawlReport {
+    pub fn failures(&self) -> impl Iterator<Item = &UrlOutcome> {
+        self.urls.iter().filter(|u| u.status.is_failure())
+    }
+}
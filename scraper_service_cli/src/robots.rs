@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+/// Bir sitenin robots.txt kuralları. Eşleşme Google'ın yorumunu izler: en uzun eşleşen
+/// kural kazanır, eşitlikte Allow önceliklidir; `*` joker ve `$` satır sonu desteklenir.
+#[derive(Debug, Clone, Default)]
+pub struct Robots {
+    groups: Vec<Group>,
+    /// `Sitemap:` satırları; hangi gruba yazıldığından bağımsızdır
+    pub sitemaps: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<Rule>,
+    crawl_delay: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+impl Robots {
+    /// Her şeye izin veren kurallar (robots.txt yoksa ya da 4xx döndüyse).
+    pub fn allow_all() -> Self {
+        Robots::default()
+    }
+
+    /// Her şeyi yasaklayan kurallar (robots.txt sunucu hatası verdiyse).
+    pub fn disallow_all() -> Self {
+        Robots {
+            groups: vec![Group {
+                agents: vec!["*".to_string()],
+                rules: vec![Rule {
+                    allow: false,
+                    pattern: "/".to_string(),
+                }],
+                crawl_delay: None,
+            }],
+            sitemaps: Vec::new(),
+        }
+    }
+
+    pub fn parse(body: &str) -> Self {
+        let mut robots = Robots::default();
+        let mut current: Option<Group> = None;
+        // Art arda gelen User-agent satırları aynı gruba aittir
+        let mut collecting_agents = false;
+
+        for line in body.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    if !collecting_agents {
+                        if let Some(group) = current.take() {
+                            robots.groups.push(group);
+                        }
+                        current = Some(Group::default());
+                    }
+                    collecting_agents = true;
+                    if let Some(group) = current.as_mut() {
+                        group.agents.push(value.to_ascii_lowercase());
+                    }
+                }
+                "allow" | "disallow" => {
+                    collecting_agents = false;
+                    // Boş Disallow "her şeye izin ver" demektir; kural eklemeye gerek yok
+                    if let (Some(group), false) = (current.as_mut(), value.is_empty()) {
+                        group.rules.push(Rule {
+                            allow: key == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    collecting_agents = false;
+                    if let (Some(group), Ok(secs)) = (current.as_mut(), value.parse::<f64>()) {
+                        if secs.is_finite() && secs >= 0.0 {
+                            group.crawl_delay = Some(Duration::from_secs_f64(secs.min(60.0)));
+                        }
+                    }
+                }
+                "sitemap" if !value.is_empty() => robots.sitemaps.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        if let Some(group) = current {
+            robots.groups.push(group);
+        }
+        robots
+    }
+
+    /// Kullanıcı ajanına uyan grup: ajan adını içeren en uzun belirteç, yoksa `*`.
+    fn group(&self, user_agent: &str) -> Option<&Group> {
+        let product = user_agent
+            .split(['/', ' '])
+            .next()
+            .unwrap_or(user_agent)
+            .to_ascii_lowercase();
+
+        let specific = self
+            .groups
+            .iter()
+            .flat_map(|g| g.agents.iter().map(move |a| (a, g)))
+            .filter(|(agent, _)| agent.as_str() != "*" && product.contains(agent.as_str()))
+            .max_by_key(|(agent, _)| agent.len())
+            .map(|(_, g)| g);
+
+        specific.or_else(|| self.groups.iter().find(|g| g.agents.iter().any(|a| a == "*")))
+    }
+
+    /// URL yolunun (sorgu dahil) bu ajan için taranıp taranamayacağı.
+    pub fn is_allowed(&self, user_agent: &str, path: &str) -> bool {
+        let Some(group) = self.group(user_agent) else {
+            return true;
+        };
+        if path == "/robots.txt" {
+            return true;
+        }
+
+        let mut best: Option<(usize, bool)> = None;
+        for rule in &group.rules {
+            if !matches(&rule.pattern, path) {
+                continue;
+            }
+            let len = rule.pattern.len();
+            best = match best {
+                Some((best_len, best_allow))
+                    if best_len > len || (best_len == len && best_allow) =>
+                {
+                    Some((best_len, best_allow))
+                }
+                _ => Some((len, rule.allow)),
+            };
+        }
+        best.map(|(_, allow)| allow).unwrap_or(true)
+    }
+
+    pub fn crawl_delay(&self, user_agent: &str) -> Option<Duration> {
+        self.group(user_agent).and_then(|g| g.crawl_delay)
+    }
+}
+
+/// robots.txt deseni: `*` herhangi bir dizi, sondaki `$` yolun bitmesi demektir.
+/// Desen ve yol karşılaştırılmadan önce yüzde kodlaması normalize edilmez; iki taraf da ham haliyle eşlenir.
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut rest = path;
+    for (i, part) in parts.iter().enumerate() {
+        if i == 0 {
+            match rest.strip_prefix(part) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        } else if i == parts.len() - 1 && anchored {
+            return rest.ends_with(part);
+        } else {
+            match rest.find(part) {
+                Some(pos) => rest = &rest[pos + part.len()..],
+                None => return false,
+            }
+        }
+    }
+    !anchored || rest.is_empty() || pattern.ends_with('*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UA: &str = "AxelteBot/0.1 (+https://github.com/axelte)";
+
+    #[test]
+    fn wildcard_and_end_anchor() {
+        assert!(matches("/private", "/private/page"));
+        assert!(matches("/*.php", "/a/b/index.php?x=1"));
+        assert!(matches("/*.php$", "/index.php"));
+        assert!(!matches("/*.php$", "/index.php?x=1"));
+        assert!(!matches("/*.php$", "/index.phps"));
+        assert!(matches("/page$", "/page"));
+        assert!(!matches("/page$", "/page/"));
+        assert!(matches("/a*b*c", "/a-x-b-y-c-z"));
+        assert!(!matches("/a*b*c", "/a-x-c-y-b"));
+        // `*` boş diziyle de eşleşir; sondaki `$` ile örtüşen kısım ikinci kez sayılmaz
+        assert!(matches("/a*b$", "/ab"));
+        assert!(!matches("/ab*b$", "/ab"));
+        assert!(matches("/*$", "/anything"));
+        assert!(!matches("/private", "/Private"));
+    }
+
+    #[test]
+    fn longest_match_wins() {
+        let robots = Robots::parse(
+            "User-agent: *\n\
+             Disallow: /docs/\n\
+             Allow: /docs/public/\n\
+             Disallow: /docs/public/drafts\n",
+        );
+        assert!(!robots.is_allowed(UA, "/docs/internal"));
+        assert!(robots.is_allowed(UA, "/docs/public/guide"));
+        assert!(!robots.is_allowed(UA, "/docs/public/drafts/1"));
+        assert!(robots.is_allowed(UA, "/blog"));
+    }
+
+    #[test]
+    fn equal_length_tie_prefers_allow() {
+        // Kuralların sırası sonucu değiştirmez
+        for body in [
+            "User-agent: *\nDisallow: /page\nAllow: /page\n",
+            "User-agent: *\nAllow: /page\nDisallow: /page\n",
+        ] {
+            assert!(Robots::parse(body).is_allowed(UA, "/page"), "{}", body);
+        }
+        let robots = Robots::parse("User-agent: *\nAllow: /a*\nDisallow: /ab\n");
+        assert!(robots.is_allowed(UA, "/abc"));
+    }
+
+    #[test]
+    fn most_specific_group_is_used() {
+        let robots = Robots::parse(
+            "User-agent: *\n\
+             Disallow: /\n\
+             \n\
+             User-agent: otherbot\n\
+             User-agent: axeltebot\n\
+             Disallow: /private/\n\
+             Crawl-delay: 2.5\n",
+        );
+        assert!(robots.is_allowed(UA, "/docs"));
+        assert!(!robots.is_allowed(UA, "/private/x"));
+        assert!(!robots.is_allowed("SomeBot/1.0", "/docs"));
+        assert_eq!(robots.crawl_delay(UA), Some(Duration::from_millis(2500)));
+        assert_eq!(robots.crawl_delay("SomeBot/1.0"), None);
+    }
+
+    #[test]
+    fn parse_edge_cases() {
+        let robots = Robots::parse(
+            "# yorum\n\
+             Sitemap: https://example.com/sitemap.xml\n\
+             User-agent: *\n\
+             Disallow:\n\
+             Disallow: /tmp # satır sonu yorumu\n\
+             Crawl-delay: 1000\n",
+        );
+        assert!(robots.is_allowed(UA, "/page"));
+        assert!(!robots.is_allowed(UA, "/tmp/x"));
+        // Crawl-delay 60 saniyeyle sınırlanır
+        assert_eq!(robots.crawl_delay(UA), Some(Duration::from_secs(60)));
+        assert_eq!(robots.sitemaps, vec!["https://example.com/sitemap.xml"]);
+
+        assert!(Robots::allow_all().is_allowed(UA, "/anything"));
+        assert!(!Robots::disallow_all().is_allowed(UA, "/anything"));
+        // robots.txt'nin kendisi her zaman indirilebilir
+        assert!(Robots::disallow_all().is_allowed(UA, "/robots.txt"));
+    }
+}
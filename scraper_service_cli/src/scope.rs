@@ -0,0 +1,319 @@
+use std::fmt;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Başlangıç adresinin host'una göre hangi host'ların taranacağı.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubdomainPolicy {
+    /// Sadece birebir aynı host
+    Exact,
+    /// Aynı host; `www.` ile başlayan ve başlamayan hali aynı sayılır
+    SameSite,
+    /// Host ve onun bütün alt alan adları (docs.example.com, blog.example.com, ...)
+    All,
+}
+
+impl FromStr for SubdomainPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "exact" => Ok(SubdomainPolicy::Exact),
+            "same-site" => Ok(SubdomainPolicy::SameSite),
+            "all" => Ok(SubdomainPolicy::All),
+            other => Err(format!(
+                "unknown subdomain policy '{}' (expected exact, same-site or all)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SubdomainPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SubdomainPolicy::Exact => "exact",
+            SubdomainPolicy::SameSite => "same-site",
+            SubdomainPolicy::All => "all",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Taramanın sınırları. Desenler URL'nin yol + sorgu kısmıyla eşlenir; `re:` ile başlayanlar
+/// düzenli ifade, diğerleri glob'dur (`*` tek yol parçası, `**` herhangi bir dizi, `?` tek karakter).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlScope {
+    /// Başlangıç sayfası 0. derinliktir; `None` sınırsız
+    pub max_depth: Option<usize>,
+    /// Boş değilse en az biriyle eşleşen adresler taranır
+    pub include: Vec<String>,
+    /// Eşleşen adresler taranmaz (include'dan önceliklidir)
+    pub exclude: Vec<String>,
+    /// Sadece bu önekle başlayan yollar (ör. `/docs/`)
+    pub path_prefix: Option<String>,
+    pub subdomains: SubdomainPolicy,
+    /// Bu uzantılarla biten yollar indirilmez (HTML olmayan dosyalar)
+    pub skip_extensions: Vec<String>,
+}
+
+impl Default for CrawlScope {
+    fn default() -> Self {
+        CrawlScope {
+            max_depth: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            path_prefix: None,
+            subdomains: SubdomainPolicy::SameSite,
+            skip_extensions: [
+                "7z", "avi", "css", "dmg", "doc", "docx", "exe", "gif", "gz", "ico", "iso", "jpeg",
+                "jpg", "js", "mov", "mp3", "mp4", "pdf", "png", "ppt", "pptx", "rar", "svg", "tar",
+                "ttf", "wav", "webm", "webp", "woff", "woff2", "xls", "xlsx", "zip",
+            ]
+            .iter()
+            .map(|e| e.to_string())
+            .collect(),
+        }
+    }
+}
+
+impl CrawlScope {
+    /// Desenleri derler; geçersiz bir düzenli ifade varsa hangisi olduğunu söyleyen hata döner.
+    pub fn compile(&self) -> Result<ScopeMatcher, String> {
+        let compile_all = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| compile_pattern(p).map_err(|e| format!("invalid pattern '{}': {}", p, e)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(ScopeMatcher {
+            include: compile_all(&self.include)?,
+            exclude: compile_all(&self.exclude)?,
+            scope: self.clone(),
+        })
+    }
+}
+
+pub(crate) fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    if let Some(re) = pattern.strip_prefix("re:") {
+        return Regex::new(re);
+    }
+
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re)
+}
+
+/// Derlenmiş kapsam kuralları.
+#[derive(Debug, Clone)]
+pub struct ScopeMatcher {
+    scope: CrawlScope,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl ScopeMatcher {
+    pub fn max_depth(&self) -> Option<usize> {
+        self.scope.max_depth
+    }
+
+    /// `depth` derinliğinde bulunan `url`'nin taranıp taranmayacağı.
+    pub fn allows(&self, base: &Url, url: &Url, depth: usize) -> bool {
+        if !matches!(url.scheme(), "http" | "https") {
+            return false;
+        }
+        if self.scope.max_depth.is_some_and(|max| depth > max) {
+            return false;
+        }
+        if !self.host_allowed(base, url) {
+            return false;
+        }
+
+        let path = url.path();
+        if let Some(prefix) = &self.scope.path_prefix {
+            if !path.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if self.skipped_extension(path) {
+            return false;
+        }
+
+        let target = match url.query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path.to_string(),
+        };
+        if self.exclude.iter().any(|re| re.is_match(&target)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|re| re.is_match(&target))
+    }
+
+    fn host_allowed(&self, base: &Url, url: &Url) -> bool {
+        let (Some(base_host), Some(host)) = (base.host_str(), url.host_str()) else {
+            return false;
+        };
+        let strip_www = |h: &str| h.strip_prefix("www.").unwrap_or(h).to_string();
+        match self.scope.subdomains {
+            SubdomainPolicy::Exact => host == base_host,
+            SubdomainPolicy::SameSite => strip_www(host) == strip_www(base_host),
+            SubdomainPolicy::All => {
+                let site = strip_www(base_host);
+                host == site || host.ends_with(&format!(".{}", site))
+            }
+        }
+    }
+
+    fn skipped_extension(&self, path: &str) -> bool {
+        let name = path.rsplit('/').next().unwrap_or("");
+        match name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => self
+                .scope
+                .skip_extensions
+                .iter()
+                .any(|skip| skip.trim_start_matches('.').eq_ignore_ascii_case(ext)),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    fn matcher(scope: CrawlScope) -> ScopeMatcher {
+        scope.compile().unwrap()
+    }
+
+    #[test]
+    fn glob_patterns() {
+        let re = compile_pattern("/docs/*").unwrap();
+        assert!(re.is_match("/docs/intro"));
+        assert!(!re.is_match("/docs/guide/intro"));
+
+        let re = compile_pattern("/docs/**").unwrap();
+        assert!(re.is_match("/docs/guide/intro"));
+        assert!(!re.is_match("/blog/docs/x"));
+
+        let re = compile_pattern("/page?").unwrap();
+        assert!(re.is_match("/page1"));
+        assert!(!re.is_match("/page12"));
+
+        // Regex özel karakterleri glob'da düz karakterdir
+        let re = compile_pattern("/a.b+(c)").unwrap();
+        assert!(re.is_match("/a.b+(c)"));
+        assert!(!re.is_match("/axb+(c)"));
+
+        let re = compile_pattern("**?lang=*").unwrap();
+        assert!(re.is_match("/docs/intro?lang=en"));
+    }
+
+    #[test]
+    fn regex_patterns() {
+        let re = compile_pattern(r"re:^/blog/\d{4}/").unwrap();
+        assert!(re.is_match("/blog/2024/post"));
+        assert!(!re.is_match("/blog/latest"));
+        assert!(compile_pattern("re:(").is_err());
+
+        let scope = CrawlScope { include: vec!["re:(".to_string()], ..CrawlScope::default() };
+        assert!(scope.compile().unwrap_err().contains("invalid pattern 're:('"));
+    }
+
+    #[test]
+    fn subdomain_policies() {
+        let base = url("https://www.example.com/");
+        let exact = matcher(CrawlScope { subdomains: SubdomainPolicy::Exact, ..CrawlScope::default() });
+        let same_site = matcher(CrawlScope::default());
+        let all = matcher(CrawlScope { subdomains: SubdomainPolicy::All, ..CrawlScope::default() });
+
+        assert!(exact.allows(&base, &url("https://www.example.com/a"), 1));
+        assert!(!exact.allows(&base, &url("https://example.com/a"), 1));
+
+        // www. ile apex aynı site sayılır, alt alan adları sayılmaz
+        assert!(same_site.allows(&base, &url("https://example.com/a"), 1));
+        assert!(same_site.allows(&url("https://example.com/"), &url("https://www.example.com/a"), 1));
+        assert!(!same_site.allows(&base, &url("https://docs.example.com/a"), 1));
+
+        assert!(all.allows(&base, &url("https://docs.example.com/a"), 1));
+        assert!(all.allows(&base, &url("https://example.com/a"), 1));
+        assert!(!all.allows(&base, &url("https://badexample.com/a"), 1));
+        assert!(!all.allows(&base, &url("https://example.com.evil.io/a"), 1));
+    }
+
+    #[test]
+    fn depth_prefix_and_scheme() {
+        let base = url("https://example.com/");
+        let scope = matcher(CrawlScope {
+            max_depth: Some(2),
+            path_prefix: Some("/docs/".to_string()),
+            ..CrawlScope::default()
+        });
+        assert!(scope.allows(&base, &url("https://example.com/docs/a"), 2));
+        assert!(!scope.allows(&base, &url("https://example.com/docs/a"), 3));
+        assert!(!scope.allows(&base, &url("https://example.com/docsx"), 1));
+        assert!(!scope.allows(&base, &url("https://example.com/blog/docs/"), 1));
+        assert!(!scope.allows(&base, &url("mailto:me@example.com"), 1));
+        assert!(!scope.allows(&base, &url("ftp://example.com/docs/a"), 1));
+        assert_eq!(scope.max_depth(), Some(2));
+    }
+
+    #[test]
+    fn include_and_exclude() {
+        let base = url("https://example.com/");
+        let scope = matcher(CrawlScope {
+            include: vec!["/docs/**".to_string(), "/whitepaper*".to_string()],
+            exclude: vec!["/docs/archive/**".to_string(), "re:[?&]print=1".to_string()],
+            ..CrawlScope::default()
+        });
+        assert!(scope.allows(&base, &url("https://example.com/docs/guide"), 1));
+        assert!(scope.allows(&base, &url("https://example.com/whitepaper-v2"), 1));
+        assert!(!scope.allows(&base, &url("https://example.com/blog"), 1));
+        // exclude include'dan önceliklidir ve sorguyla da eşlenir
+        assert!(!scope.allows(&base, &url("https://example.com/docs/archive/2019"), 1));
+        assert!(!scope.allows(&base, &url("https://example.com/docs/guide?print=1"), 1));
+    }
+
+    #[test]
+    fn skipped_extensions() {
+        let base = url("https://example.com/");
+        let scope = matcher(CrawlScope::default());
+        assert!(!scope.allows(&base, &url("https://example.com/files/report.PDF"), 1));
+        assert!(!scope.allows(&base, &url("https://example.com/video.mp4?x=1"), 1));
+        assert!(scope.allows(&base, &url("https://example.com/docs/v1.2/intro"), 1));
+        assert!(scope.allows(&base, &url("https://example.com/page.html"), 1));
+        // Nokta ile başlayan ad uzantı sayılmaz
+        assert!(scope.allows(&base, &url("https://example.com/.zip"), 1));
+
+        let custom = matcher(CrawlScope { skip_extensions: vec![".html".to_string()], ..CrawlScope::default() });
+        assert!(!custom.allows(&base, &url("https://example.com/page.html"), 1));
+        assert!(custom.allows(&base, &url("https://example.com/file.zip"), 1));
+    }
+
+    #[test]
+    fn subdomain_policy_parsing() {
+        assert_eq!("Same-Site".parse::<SubdomainPolicy>(), Ok(SubdomainPolicy::SameSite));
+        assert_eq!(SubdomainPolicy::All.to_string(), "all");
+        assert!("subdomains".parse::<SubdomainPolicy>().is_err());
+    }
+}
@@ -1,128 +1,589 @@
 use crate::{Page, Heading};
-use futures::stream::{self, StreamExt};
-use reqwest::Client;
+use crate::config::ScraperConfig;
+use crate::error::FetchError;
+use crate::frontier::{Frontier, RuleScorer, UrlScorer};
+use crate::politeness::{self, HostLimiter};
+use crate::report::{CrawlReport, StopReason, UrlOutcome, UrlStatus};
+use crate::robots::Robots;
+use crate::scope::ScopeMatcher;
+use crate::sitemap::{self, Sitemap, SitemapEntry};
+use futures::stream::{FuturesUnordered, StreamExt};
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::{header::{CONTENT_TYPE, LOCATION}, Client, Response, StatusCode};
 use scraper::{Html, Selector};
-use std::{collections::HashSet, time::Duration};
-use tokio::sync::Mutex;
+use std::{collections::{HashMap, HashSet, VecDeque}, sync::{atomic::{AtomicU32, AtomicU64, Ordering}, Arc}, time::Duration};
+use tokio::{sync::{Mutex, OwnedSemaphorePermit}, time::Instant};
+use tracing::{debug, info, instrument, warn};
 use url::Url;
-use backoff::{ExponentialBackoff, future::retry};
+use backoff::future::retry;
 use chrono::Utc;
 
+/// `Scraper::get`'in yönlendirme adımlarını nasıl denetleyeceği.
+#[derive(Clone, Copy)]
+enum Hops {
+    /// robots.txt: host sınırı ve kapsam denetimi yok
+    Unchecked,
+    /// Sitemap: her adım hedef host'un limiter'ından geçer
+    Throttled,
+    /// Sayfa: her adım ayrıca kapsam ve robots.txt'ye göre denetlenir
+    Crawl { depth: usize },
+}
+
+enum Fetched {
+    /// Son yanıt; izin gövde okunana kadar tutulmalıdır
+    Response {
+        response: Response,
+        redirects: Vec<String>,
+        permit: Option<OwnedSemaphorePermit>,
+    },
+    /// Yönlendirme kapsam dışına ya da robots.txt'nin yasakladığı bir adrese gitti
+    Stopped {
+        status: UrlStatus,
+        redirects: Vec<String>,
+    },
+}
+
+/// robots.txt kurallarının eşlendiği yol + sorgu.
+fn robots_path(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    }
+}
+
+/// Tek bir ziyaretin sonucu; `visit_with_retry` bunu rapora yazar.
+struct Visited {
+    status: UrlStatus,
+    links: Vec<(Url, usize)>,
+    redirects: Vec<String>,
+    bytes: u64,
+}
+
 pub struct Scraper {
     client: Client,
     base_url: Url,
+    /// Ziyaret edilen (normalize edilmiş) adresler
     visited: Mutex<HashSet<String>>,
+    /// Kaydedilen sayfaların canonical adresleri; aynı içerik iki kez kaydedilmez
+    recorded: Mutex<HashSet<String>>,
     pages: Mutex<Vec<Page>>,
-    max_pages: usize,
-    concurrent_requests: usize,
-    max_content_length: usize,
+    /// Denenen her adresin sonucu
+    outcomes: Mutex<Vec<UrlOutcome>>,
+    config: ScraperConfig,
+    /// Host başına robots.txt önbelleği (anahtar: scheme://host:port)
+    robots: Mutex<HashMap<String, Arc<Robots>>>,
+    /// Host başına istek hızı ve eşzamanlılık sınırı
+    limiter: HostLimiter,
+    /// `config.scope`'un derlenmiş hali
+    scope: ScopeMatcher,
+    scorer: Arc<dyn UrlScorer>,
+    /// İndirilen sayfa gövdelerinin toplam boyutu
+    bytes_downloaded: AtomicU64,
 }
 
+/// Sitemap index'leri üzerinden indirilecek en fazla sitemap dosyası
+const MAX_SITEMAP_FILES: usize = 50;
+const MAX_SITEMAP_ENTRIES: usize = 50_000;
+
 impl Scraper {
-    pub fn new(start_url: &str, max_pages: usize, concurrent_requests: usize, max_content_length: usize) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(start_url: &str, config: ScraperConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        config.validate()?;
         Ok(Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(30))
-                .build()?,
+            client: config.build_client()?,
             base_url: Url::parse(start_url)?,
             visited: Mutex::new(HashSet::new()),
+            recorded: Mutex::new(HashSet::new()),
             pages: Mutex::new(Vec::new()),
-            max_pages,
-            concurrent_requests,
-            max_content_length,
+            outcomes: Mutex::new(Vec::new()),
+            robots: Mutex::new(HashMap::new()),
+            limiter: HostLimiter::new(
+                config.host_burst,
+                config.max_concurrent_per_host,
+                Duration::from_secs(config.max_retry_after_secs),
+            ),
+            scope: config.scope.compile()?,
+            scorer: Arc::new(RuleScorer::new(&config.priority)?),
+            bytes_downloaded: AtomicU64::new(0),
+            config,
         })
     }
 
-    pub async fn scrape(&self, start_url: &str) -> Result<Vec<Page>, Box<dyn std::error::Error>> {
-        let mut urls_to_visit = vec![start_url.to_string()];
-        
-        while !urls_to_visit.is_empty() {
-            let batch: Vec<_> = urls_to_visit.drain(..self.concurrent_requests.min(urls_to_visit.len())).collect();
-            
-            let mut new_urls = stream::iter(batch)
-                .map(|url| self.visit_with_retry(url))
-                .buffer_unordered(self.concurrent_requests)
-                .collect::<Vec<_>>()
-                .await;
-
-            urls_to_visit.append(&mut new_urls.into_iter().flatten().collect());
-            
-            if self.pages.lock().await.len() >= self.max_pages {
+    /// `config.priority` kuralları yerine kullanılacak puanlayıcı.
+    pub fn with_scorer(mut self, scorer: impl UrlScorer + 'static) -> Self {
+        self.scorer = Arc::new(scorer);
+        self
+    }
+
+    pub fn config(&self) -> &ScraperConfig {
+        &self.config
+    }
+
+    /// Host'un robots.txt'sini bir kez indirir ve önbellekte tutar. 4xx "kısıtlama yok",
+    /// 5xx ya da bağlantı hatası "geçici olarak her şey yasak" olarak yorumlanır.
+    async fn robots_for(&self, url: &Url) -> Arc<Robots> {
+        let origin = url.origin().ascii_serialization();
+        if let Some(robots) = self.robots.lock().await.get(&origin) {
+            return robots.clone();
+        }
+
+        let robots_url = format!("{}/robots.txt", origin);
+        let robots_url = Url::parse(&robots_url).expect("origin is a valid URL");
+        let robots = match self.get(robots_url, Hops::Unchecked, Duration::ZERO).await {
+            Ok(Fetched::Response { response, .. }) if response.status().is_success() => {
+                match response.text().await {
+                    Ok(body) => Robots::parse(&body),
+                    Err(_) => Robots::disallow_all(),
+                }
+            }
+            Ok(Fetched::Response { response, .. }) if response.status().is_client_error() => {
+                Robots::allow_all()
+            }
+            _ => Robots::disallow_all(),
+        };
+
+        let robots = Arc::new(robots);
+        self.robots.lock().await.insert(origin, robots.clone());
+        robots
+    }
+
+    /// robots.txt'de Crawl-delay yoksa aynı host'a art arda istekler arasındaki bekleme.
+    fn crawl_delay(&self) -> Duration {
+        Duration::from_millis(self.config.crawl_delay_ms)
+    }
+
+    /// GET isteği yapar ve yönlendirmeleri `config.max_redirects`'e kadar izler. `delay` ilk
+    /// adresin host'u için istekler arası beklemedir; başka host'a geçen adımlar o host'un
+    /// robots.txt Crawl-delay'ini kullanır. Geçilen adresler (son adres dahil) sırasıyla döner.
+    async fn get(&self, url: Url, hops: Hops, delay: Duration) -> Result<Fetched, FetchError> {
+        let mut current = url;
+        let mut delay = delay;
+        let mut redirects = Vec::new();
+        loop {
+            // Önceki adımın izni döngü sonunda bırakılır; aynı host'a yönlendirme kilitlenmez
+            let permit = match hops {
+                Hops::Unchecked => None,
+                Hops::Throttled | Hops::Crawl { .. } => Some(self.limiter.acquire(&current, delay).await),
+            };
+            let res = self.client.get(current.clone()).send().await?;
+            let location = res
+                .headers()
+                .get(LOCATION)
+                .and_then(|l| l.to_str().ok())
+                .filter(|_| res.status().is_redirection());
+            let Some(location) = location else {
+                return Ok(Fetched::Response { response: res, redirects, permit });
+            };
+            if redirects.len() >= self.config.max_redirects {
+                return Err(FetchError::TooManyRedirects);
+            }
+            let next = current.join(location)?;
+            redirects.push(next.to_string());
+
+            let host_changed = next.host_str() != current.host_str() || next.port() != current.port();
+            if host_changed {
+                delay = self.crawl_delay();
+            }
+            if let Hops::Crawl { depth } = hops {
+                if !self.scope.allows(&self.base_url, &next, depth) {
+                    info!(location = %next, "redirected out of scope");
+                    return Ok(Fetched::Stopped {
+                        status: UrlStatus::RedirectedOutOfScope { location: next.into() },
+                        redirects,
+                    });
+                }
+                if self.config.respect_robots {
+                    // robots_for da `get`'i çağırır; döngüsel async çağrı kutulanmalı
+                    let robots = Box::pin(self.robots_for(&next)).await;
+                    if !robots.is_allowed(&self.config.user_agent, &robots_path(&next)) {
+                        info!(location = %next, "redirect target disallowed by robots.txt");
+                        return Ok(Fetched::Stopped { status: UrlStatus::SkippedByRobots, redirects });
+                    }
+                    if host_changed {
+                        if let Some(crawl_delay) = robots.crawl_delay(&self.config.user_agent) {
+                            delay = crawl_delay;
+                        }
+                    }
+                }
+            }
+            current = next;
+        }
+    }
+
+    #[instrument(name = "scrape", skip_all, fields(start_url = %start_url))]
+    pub async fn scrape(&self, start_url: &str) -> Result<CrawlReport, Box<dyn std::error::Error>> {
+        let started = Instant::now();
+        let started_at = Utc::now();
+        let deadline = self
+            .config
+            .max_duration_secs
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+
+        // Başlangıç sayfası ve sitemap adresleri 0. derinliktir
+        let mut frontier = Frontier::new(self.config.max_frontier, self.scorer.clone());
+        if !self.config.sitemap_only {
+            frontier.push(&self.config.normalizer.normalize(&Url::parse(start_url)?), 0);
+        }
+        if self.config.use_sitemaps || self.config.sitemap_only {
+            for entry in self.discover_sitemaps().await {
+                if let Ok(url) = Url::parse(&entry.loc) {
+                    frontier.push(&self.config.normalizer.normalize(&url), 0);
+                }
+            }
+        }
+        if self.config.sitemap_only && frontier.is_empty() {
+            return Err("no URLs found in sitemaps".into());
+        }
+
+        let mut in_flight = FuturesUnordered::new();
+        let mut timed_out = false;
+        loop {
+            while in_flight.len() < self.config.concurrent_requests && !self.budget_exhausted(in_flight.len()).await {
+                let Some((url, depth)) = frontier.pop() else {
+                    break;
+                };
+                in_flight.push(self.visit_with_retry(url, depth));
+            }
+
+            let finished = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, in_flight.next()).await {
+                    Ok(finished) => finished,
+                    Err(_) => {
+                        info!("time budget reached, stopping crawl");
+                        timed_out = true;
+                        break;
+                    }
+                },
+                None => in_flight.next().await,
+            };
+            // Sürmekte olan istek kalmadıysa ya kuyruk boştur ya da bütçe dolmuştur
+            let Some(links) = finished else {
                 break;
+            };
+
+            if !self.config.sitemap_only {
+                for (link, depth) in links {
+                    frontier.push(&link, depth);
+                }
             }
         }
 
-        Ok(self.pages.lock().await.clone())
+        let pages = self.pages.lock().await.clone();
+        let bytes_downloaded = self.bytes_downloaded.load(Ordering::Relaxed);
+        let stop_reason = if timed_out {
+            StopReason::MaxDuration
+        } else if pages.len() >= self.config.max_pages {
+            StopReason::MaxPages
+        } else if self.config.max_bytes.is_some_and(|max| bytes_downloaded >= max) {
+            StopReason::MaxBytes
+        } else {
+            StopReason::Completed
+        };
+
+        info!(
+            pages = pages.len(),
+            bytes = bytes_downloaded,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            ?stop_reason,
+            "crawl finished"
+        );
+        Ok(CrawlReport {
+            start_url: start_url.to_string(),
+            started_at,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            bytes_downloaded,
+            stop_reason,
+            pages,
+            urls: self.outcomes.lock().await.clone(),
+        })
     }
 
-    async fn visit_with_retry(&self, url: String) -> Vec<String> {
-        let backoff = ExponentialBackoff::default();
-        
-        match retry(backoff, || async {
-            match self.visit(&url).await {
-                Ok(urls) => Ok(urls),
-                Err(e) => {
-                    eprintln!("❌ {} için hata: {}", url, e);
-                    Err(backoff::Error::transient(e))
+    /// Yeni istek başlatılabilir mi? Sürmekte olan her istek bir sayfa sayılır; böylece
+    /// aynı anda yapılan istekler `max_pages` sınırını aşamaz.
+    async fn budget_exhausted(&self, in_flight: usize) -> bool {
+        if self.pages.lock().await.len() + in_flight >= self.config.max_pages {
+            return true;
+        }
+        self.config
+            .max_bytes
+            .is_some_and(|max| self.bytes_downloaded.load(Ordering::Relaxed) >= max)
+    }
+
+    /// robots.txt'deki `Sitemap:` satırlarından (yoksa /sitemap.xml'den) başlayarak sitemap
+    /// index'lerini izler ve sitedeki adresleri önceliğe göre sıralı döndürür.
+    #[instrument(skip_all)]
+    pub async fn discover_sitemaps(&self) -> Vec<SitemapEntry> {
+        let mut queue: VecDeque<String> = self.robots_for(&self.base_url).await.sitemaps.iter().cloned().collect();
+        if queue.is_empty() {
+            queue.push_back(format!("{}/sitemap.xml", self.base_url.origin().ascii_serialization()));
+        }
+
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        while let Some(sitemap_url) = queue.pop_front() {
+            if seen.len() >= MAX_SITEMAP_FILES || entries.len() >= MAX_SITEMAP_ENTRIES {
+                break;
+            }
+            if !seen.insert(sitemap_url.clone()) {
+                continue;
+            }
+
+            match self.fetch_sitemap(&sitemap_url).await {
+                Ok(Sitemap::Index(children)) => queue.extend(children),
+                Ok(Sitemap::UrlSet(urls)) => entries.extend(urls.into_iter().filter(|e| {
+                    Url::parse(&e.loc)
+                        .map(|u| self.scope.allows(&self.base_url, &u, 0))
+                        .unwrap_or(false)
+                })),
+                Err(e) => warn!(sitemap = %sitemap_url, error = %e, "failed to read sitemap"),
+            }
+        }
+
+        entries.truncate(MAX_SITEMAP_ENTRIES);
+        sitemap::sort_by_priority(&mut entries);
+        debug!(sitemaps = seen.len(), urls = entries.len(), "sitemaps discovered");
+        entries
+    }
+
+    async fn fetch_sitemap(&self, url: &str) -> Result<Sitemap, String> {
+        let parsed = Url::parse(url).map_err(|e| e.to_string())?;
+        let fetched = self
+            .get(parsed, Hops::Throttled, self.crawl_delay())
+            .await
+            .map_err(|e| e.to_string())?;
+        let Fetched::Response { response: mut res, permit: _permit, .. } = fetched else {
+            return Err("sitemap redirect was not followed".to_string());
+        };
+        if matches!(res.status(), StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE) {
+            self.limiter.throttle(res.url(), politeness::retry_after(res.headers())).await;
+        }
+        if !res.status().is_success() {
+            return Err(format!("HTTP {}", res.status()));
+        }
+        if res.content_length().is_some_and(|len| len > sitemap::MAX_SITEMAP_BYTES) {
+            return Err("sitemap is too large".to_string());
+        }
+        // Content-Length olmayan (chunked) yanıtlar da parça parça okunup sınırda kesilir
+        let mut body = Vec::new();
+        while let Some(chunk) = res.chunk().await.map_err(|e| e.to_string())? {
+            if (body.len() + chunk.len()) as u64 > sitemap::MAX_SITEMAP_BYTES {
+                return Err("sitemap is too large".to_string());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        sitemap::parse(&body)
+    }
+
+    /// Adresi bir kez ziyaret edilmiş sayar, robots.txt'yi denetler ve geçici hatalarda
+    /// `config.retry`'a göre yeniden dener. Sonuç rapora yazılır.
+    #[instrument(name = "visit", skip_all, fields(url = %url, depth = depth))]
+    async fn visit_with_retry(&self, url: String, depth: usize) -> Vec<(Url, usize)> {
+        let parsed = match Url::parse(&url) {
+            Ok(parsed) => self.config.normalizer.normalize(&parsed),
+            Err(e) => {
+                self.record(UrlOutcome::new(url, depth, UrlStatus::from(FetchError::from(e)))).await;
+                return Vec::new();
+            }
+        };
+        if !self.visited.lock().await.insert(parsed.to_string()) {
+            return Vec::new();
+        }
+
+        let mut delay = self.crawl_delay();
+        if self.config.respect_robots {
+            let robots = self.robots_for(&parsed).await;
+            if !robots.is_allowed(&self.config.user_agent, &robots_path(&parsed)) {
+                info!("disallowed by robots.txt");
+                self.record(UrlOutcome::new(parsed.into(), depth, UrlStatus::SkippedByRobots)).await;
+                return Vec::new();
+            }
+            if let Some(crawl_delay) = robots.crawl_delay(&self.config.user_agent) {
+                delay = crawl_delay;
+            }
+        }
+
+        let started = Instant::now();
+        let attempts = AtomicU32::new(0);
+        let result = retry(self.config.retry.backoff(), || async {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            self.visit(&parsed, depth, delay).await.map_err(|e| {
+                if e.is_transient() && attempt < self.config.retry.max_attempts {
+                    warn!(attempt, error = %e, "transient error, retrying");
+                    backoff::Error::transient(e)
+                } else {
+                    backoff::Error::permanent(e)
                 }
+            })
+        })
+        .await;
+
+        let mut outcome = UrlOutcome::new(parsed.to_string(), depth, UrlStatus::Fetched);
+        outcome.attempts = attempts.load(Ordering::Relaxed);
+        outcome.elapsed_ms = started.elapsed().as_millis() as u64;
+        let links = match result {
+            Ok(visited) => {
+                outcome.status = visited.status;
+                outcome.redirects = visited.redirects;
+                outcome.bytes = visited.bytes;
+                visited.links
             }
-        }).await {
-            Ok(urls) => urls,
             Err(e) => {
-                eprintln!("❌ {} için maksimum deneme sayısına ulaşıldı: {}", url, e);
+                warn!(attempts = outcome.attempts, error = %e, "fetch failed");
+                outcome.status = e.into();
                 Vec::new()
             }
-        }
+        };
+        debug!(status = ?outcome.status, elapsed_ms = outcome.elapsed_ms, bytes = outcome.bytes, "visited");
+        self.record(outcome).await;
+        links
     }
 
-    async fn visit(&self, url: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        if !self.visited.lock().await.insert(url.to_string()) {
-            return Ok(Vec::new());
-        }
+    async fn record(&self, outcome: UrlOutcome) {
+        self.outcomes.lock().await.push(outcome);
+    }
 
-        println!("🔍 Ziyaret ediliyor: {}", url);
-        
-        // Rate limiting için kısa bir bekleme
-        tokio::time::sleep(Duration::from_millis(500)).await;
-        
-        let res = self.client.get(url).send().await?;
+    /// Sayfayı bir kez indirip kaydeder ve kapsam içindeki bağlantıları bir sonraki derinlikle döndürür.
+    async fn visit(&self, parsed: &Url, depth: usize, delay: Duration) -> Result<Visited, FetchError> {
+        debug!("fetching");
 
-        if !res.status().is_success() {
-            eprintln!("❌ {} HTTP error: {}", url, res.status());
-            return Ok(Vec::new());
+        // İzin gövde okunana kadar tutulur
+        let (res, redirects, _permit) = match self.get(parsed.clone(), Hops::Crawl { depth }, delay).await? {
+            Fetched::Response { response, redirects, permit } => (response, redirects, permit),
+            Fetched::Stopped { status, redirects } => {
+                return Ok(Visited { status, links: Vec::new(), redirects, bytes: 0 });
+            }
+        };
+        let status = res.status();
+        if matches!(status, StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE) {
+            // Host Retry-After kadar bekletilir; yeniden deneme limiter'dan geçtiği için bunu bekler
+            self.limiter.throttle(res.url(), politeness::retry_after(res.headers())).await;
+            return Err(FetchError::from_status(status));
         }
+        self.limiter.success(res.url()).await;
+        if !status.is_success() {
+            return Err(FetchError::from_status(status));
+        }
+
+        let mut visited = Visited {
+            status: UrlStatus::Fetched,
+            links: Vec::new(),
+            redirects,
+            bytes: 0,
+        };
+
+        // Yönlendirmeden sonraki adres; göreli bağlantılar buna göre çözülür
+        let final_url = self.config.normalizer.normalize(res.url());
 
         if let Some(server) = res.headers().get("server") {
-            let srv = server.to_str()?.to_lowercase();
+            let srv = server.to_str().unwrap_or("").to_lowercase();
             if srv.contains("cloudflare") {
-                eprintln!("⚠️ {} Cloudflare korumalı, scraping durduruluyor.", url);
-                return Ok(Vec::new());
+                warn!("behind Cloudflare, skipping");
+                visited.status = UrlStatus::Blocked { reason: "cloudflare".to_string() };
+                return Ok(visited);
             }
         }
 
-        let body = res.text().await?;
+        let Some(body) = self.read_body(res, &mut visited.bytes).await? else {
+            info!(bytes = visited.bytes, "body exceeds the byte budget, skipping");
+            visited.status = UrlStatus::OverBudget;
+            return Ok(visited);
+        };
         if body.contains("Attention Required!") || body.contains("Checking your browser") {
-            eprintln!("⚠️ {} bot koruma sayfası döndürüyor.", url);
-            return Ok(Vec::new());
+            warn!("bot protection page returned, skipping");
+            visited.status = UrlStatus::Blocked { reason: "bot protection page".to_string() };
+            return Ok(visited);
         }
 
         let doc = Html::parse_document(&body);
-        self.pages.lock().await.push(self.format_page(url, &doc));
 
-        let mut new_urls = Vec::new();
+        // Aynı içeriğe farklı adreslerden (yönlendirme, canonical) ulaşıldıysa sayfa bir kez kaydedilir
+        let page_url = self.canonical_url(&doc, &final_url).unwrap_or(final_url.clone());
+        {
+            let mut seen = self.visited.lock().await;
+            seen.insert(final_url.to_string());
+            seen.insert(page_url.to_string());
+        }
+        if !self.recorded.lock().await.insert(page_url.to_string()) {
+            visited.status = UrlStatus::Duplicate { canonical: page_url.into() };
+            return Ok(visited);
+        }
+        {
+            let mut pages = self.pages.lock().await;
+            if pages.len() >= self.config.max_pages {
+                visited.status = UrlStatus::OverBudget;
+                return Ok(visited);
+            }
+            pages.push(self.format_page(page_url.as_str(), &doc));
+        }
+
+        if self.scope.max_depth().is_some_and(|max| depth >= max) {
+            return Ok(visited);
+        }
         let link_selector = Selector::parse("a").unwrap();
         
         for el in doc.select(&link_selector) {
             if let Some(href) = el.value().attr("href") {
-                if let Ok(link) = self.base_url.join(href) {
-                    if link.domain() == self.base_url.domain() {
-                        new_urls.push(link.into_string());
+                if let Ok(link) = final_url.join(href) {
+                    let link = self.config.normalizer.normalize(&link);
+                    if self.scope.allows(&self.base_url, &link, depth + 1) {
+                        visited.links.push((link, depth + 1));
                     }
                 }
             }
         }
 
-        Ok(new_urls)
+        Ok(visited)
+    }
+
+    /// Gövdeyi parça parça okur ve `Content-Type`'taki karakter kümesiyle çözer. Gövde
+    /// `max_page_bytes`'ı ya da `max_bytes`'tan kalan bütçeyi aşarsa okuma bırakılır ve `None`
+    /// döner; okunan baytlar her durumda `bytes`'a ve toplam sayaca eklenir.
+    async fn read_body(&self, mut res: Response, bytes: &mut u64) -> Result<Option<String>, FetchError> {
+        let over_budget = |total: u64| self.config.max_bytes.is_some_and(|max| total > max);
+        let downloaded = self.bytes_downloaded.load(Ordering::Relaxed);
+        if res
+            .content_length()
+            .is_some_and(|len| len > self.config.max_page_bytes || over_budget(downloaded + len))
+        {
+            return Ok(None);
+        }
+
+        let encoding = res
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| {
+                value
+                    .split(';')
+                    .filter_map(|param| param.trim().split_once('='))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+                    .and_then(|(_, charset)| Encoding::for_label(charset.trim().trim_matches('"').as_bytes()))
+            })
+            .unwrap_or(UTF_8);
+
+        let mut body = Vec::new();
+        while let Some(chunk) = res.chunk().await? {
+            let len = chunk.len() as u64;
+            *bytes += len;
+            let total = self.bytes_downloaded.fetch_add(len, Ordering::Relaxed) + len;
+            if *bytes > self.config.max_page_bytes || over_budget(total) {
+                return Ok(None);
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let (text, _, _) = encoding.decode(&body);
+        Ok(Some(text.into_owned()))
+    }
+
+    /// `<link rel="canonical">` adresi; sadece aynı host'u gösteriyorsa dikkate alınır.
+    fn canonical_url(&self, doc: &Html, page_url: &Url) -> Option<Url> {
+        let selector = Selector::parse(r#"link[rel~="canonical"]"#).unwrap();
+        let href = doc.select(&selector).next()?.value().attr("href")?;
+        let canonical = page_url.join(href.trim()).ok()?;
+        if canonical.host_str() != page_url.host_str() {
+            return None;
+        }
+        Some(self.config.normalizer.normalize(&canonical))
     }
 
     fn format_page(&self, url: &str, doc: &Html) -> Page {
@@ -182,8 +643,8 @@ impl Scraper {
         }
 
         // Truncate content if needed
-        if page.content_length() > self.max_content_length {
-            page.truncate_content(self.max_content_length);
+        if page.content_length() > self.config.max_content_length {
+            page.truncate_content(self.config.max_content_length);
         }
 
         // Generate extract (first paragraph or meta description)
@@ -0,0 +1,95 @@
+use std::io::Read;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+
+/// Protokolün izin verdiği en büyük (açılmış) sitemap boyutu
+pub const MAX_SITEMAP_BYTES: u64 = 50 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SitemapEntry {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+    pub priority: Option<f32>,
+}
+
+#[derive(Debug)]
+pub enum Sitemap {
+    /// `<urlset>`: taranacak sayfalar
+    UrlSet(Vec<SitemapEntry>),
+    /// `<sitemapindex>`: başka sitemap dosyalarının adresleri
+    Index(Vec<String>),
+}
+
+/// Sitemap gövdesini çözer; gzip ile sıkıştırılmışsa önce açar.
+pub fn parse(body: &[u8]) -> Result<Sitemap, String> {
+    let text = if body.starts_with(&[0x1f, 0x8b]) {
+        let mut out = String::new();
+        GzDecoder::new(body)
+            .take(MAX_SITEMAP_BYTES + 1)
+            .read_to_string(&mut out)
+            .map_err(|e| format!("gzip error: {}", e))?;
+        if out.len() as u64 > MAX_SITEMAP_BYTES {
+            return Err("sitemap is too large".to_string());
+        }
+        out
+    } else {
+        String::from_utf8_lossy(body).into_owned()
+    };
+
+    let doc = roxmltree::Document::parse(text.trim_start_matches('\u{feff}'))
+        .map_err(|e| format!("XML error: {}", e))?;
+    let root = doc.root_element();
+    let child_text = |node: roxmltree::Node, name: &str| {
+        node.children()
+            .find(|c| c.tag_name().name() == name)
+            .and_then(|c| c.text())
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    };
+
+    match root.tag_name().name() {
+        "urlset" => Ok(Sitemap::UrlSet(
+            root.children()
+                .filter(|n| n.tag_name().name() == "url")
+                .filter_map(|n| {
+                    Some(SitemapEntry {
+                        loc: child_text(n, "loc")?,
+                        lastmod: child_text(n, "lastmod").and_then(|d| parse_lastmod(&d)),
+                        priority: child_text(n, "priority")
+                            .and_then(|p| p.parse::<f32>().ok())
+                            .map(|p| p.clamp(0.0, 1.0)),
+                    })
+                })
+                .collect(),
+        )),
+        "sitemapindex" => Ok(Sitemap::Index(
+            root.children()
+                .filter(|n| n.tag_name().name() == "sitemap")
+                .filter_map(|n| child_text(n, "loc"))
+                .collect(),
+        )),
+        other => Err(format!("unexpected root element <{}>", other)),
+    }
+}
+
+/// W3C Datetime: tam zaman damgası ya da sadece tarih (YYYY-MM-DD).
+fn parse_lastmod(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+}
+
+/// Önce yüksek `priority` (belirtilmemişse protokol varsayılanı 0.5), sonra en yeni `lastmod`.
+pub fn sort_by_priority(entries: &mut [SitemapEntry]) {
+    entries.sort_by(|a, b| {
+        let pa = a.priority.unwrap_or(0.5);
+        let pb = b.priority.unwrap_or(0.5);
+        pb.total_cmp(&pa).then_with(|| b.lastmod.cmp(&a.lastmod))
+    });
+}
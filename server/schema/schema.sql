@@ -17,6 +17,7 @@ CREATE TABLE workspaces (
   name VARCHAR(255) NOT NULL,
   description TEXT NULL,
   owner_id CHAR(36) NOT NULL,
+  allow_viewer_comments BOOLEAN NOT NULL DEFAULT FALSE,
   created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
   updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
   FOREIGN KEY (owner_id) REFERENCES users(id)
@@ -35,6 +36,28 @@ CREATE TABLE workspace_members (
 CREATE TABLE pages (
   id CHAR(36) PRIMARY KEY,
   workspace_id CHAR(36) NOT NULL,
+  parent_id CHAR(36) NULL,
+  title VARCHAR(255) NOT NULL,
+  content LONGTEXT NULL,
+  content_text LONGTEXT NULL,                       -- arama için içerikten çıkarılan düz metin
+  created_by CHAR(36) NOT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  deleted_at TIMESTAMP NULL DEFAULT NULL,
+  deleted_by CHAR(36) NULL,
+  INDEX idx_pages_trash (workspace_id, deleted_at),
+  FULLTEXT INDEX ft_pages_search (title, content_text),
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+  FOREIGN KEY (parent_id)    REFERENCES pages(id)      ON DELETE CASCADE,
+  FOREIGN KEY (created_by)   REFERENCES users(id),
+  FOREIGN KEY (deleted_by)   REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE TABLE page_templates (
+  id CHAR(36) PRIMARY KEY,
+  workspace_id CHAR(36) NULL,                       -- NULL: tüm workspace'lerde kullanılabilir
+  name VARCHAR(255) NOT NULL,
+  description TEXT NULL,
   title VARCHAR(255) NOT NULL,
   content LONGTEXT NULL,
   created_by CHAR(36) NOT NULL,
@@ -42,4 +65,144 @@ CREATE TABLE pages (
   updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
   FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
   FOREIGN KEY (created_by)   REFERENCES users(id)
-);
\ No newline at end of file
+);
+
+CREATE TABLE workspace_templates (
+  id CHAR(36) PRIMARY KEY,
+  name VARCHAR(255) NOT NULL,
+  description TEXT NULL,
+  pages LONGTEXT NOT NULL,                          -- JSON sayfa ağacı: [{title, content, children}]
+  created_by CHAR(36) NOT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  FOREIGN KEY (created_by) REFERENCES users(id)
+);
+
+CREATE TABLE page_links (
+  source_page_id CHAR(36) NOT NULL,
+  target_page_id CHAR(36) NOT NULL,
+  block_id VARCHAR(64) NOT NULL,
+  PRIMARY KEY (source_page_id, target_page_id, block_id),
+  INDEX idx_page_links_target (target_page_id),
+  FOREIGN KEY (source_page_id) REFERENCES pages(id) ON DELETE CASCADE,
+  FOREIGN KEY (target_page_id) REFERENCES pages(id) ON DELETE CASCADE
+);
+
+CREATE TABLE page_mentions (
+  page_id CHAR(36) NOT NULL,
+  user_id CHAR(36) NOT NULL,
+  block_id VARCHAR(64) NOT NULL,
+  mentioned_by CHAR(36) NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (page_id, user_id, block_id),
+  INDEX idx_page_mentions_user (user_id, created_at),
+  FOREIGN KEY (page_id)      REFERENCES pages(id) ON DELETE CASCADE,
+  FOREIGN KEY (user_id)      REFERENCES users(id) ON DELETE CASCADE,
+  FOREIGN KEY (mentioned_by) REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE TABLE page_comments (
+  id CHAR(36) PRIMARY KEY,
+  page_id CHAR(36) NOT NULL,
+  parent_id CHAR(36) NULL,                          -- yanıtlarda başlığın kök yorumu
+  block_id VARCHAR(64) NULL,
+  author_id CHAR(36) NOT NULL,
+  body TEXT NOT NULL,
+  resolved_at TIMESTAMP NULL DEFAULT NULL,
+  resolved_by CHAR(36) NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  INDEX idx_page_comments_page (page_id, created_at),
+  FOREIGN KEY (page_id)     REFERENCES pages(id)         ON DELETE CASCADE,
+  FOREIGN KEY (parent_id)   REFERENCES page_comments(id) ON DELETE CASCADE,
+  FOREIGN KEY (author_id)   REFERENCES users(id)         ON DELETE CASCADE,
+  FOREIGN KEY (resolved_by) REFERENCES users(id)         ON DELETE SET NULL
+);
+
+CREATE TABLE notifications (
+  id CHAR(36) PRIMARY KEY,
+  user_id CHAR(36) NOT NULL,
+  kind VARCHAR(64) NOT NULL,                        -- workspace_invite, mention, page_renamed, ...
+  actor_id CHAR(36) NULL,
+  workspace_id CHAR(36) NULL,
+  page_id CHAR(36) NULL,
+  data LONGTEXT NULL,
+  read_at TIMESTAMP NULL DEFAULT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  INDEX idx_notifications_user (user_id, read_at, created_at),
+  FOREIGN KEY (user_id)      REFERENCES users(id)      ON DELETE CASCADE,
+  FOREIGN KEY (actor_id)     REFERENCES users(id)      ON DELETE SET NULL,
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+  FOREIGN KEY (page_id)      REFERENCES pages(id)      ON DELETE SET NULL
+);
+
+CREATE TABLE webhooks (
+  id CHAR(36) PRIMARY KEY,
+  workspace_id CHAR(36) NOT NULL,
+  url VARCHAR(2048) NOT NULL,
+  secret VARCHAR(255) NOT NULL,                     -- HMAC-SHA256 imza anahtarı
+  events TEXT NOT NULL,                             -- JSON dizi; boşsa bütün olaylar
+  active BOOLEAN NOT NULL DEFAULT TRUE,
+  created_by CHAR(36) NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+  FOREIGN KEY (created_by)   REFERENCES users(id)      ON DELETE SET NULL
+);
+
+CREATE TABLE webhook_deliveries (
+  id CHAR(36) PRIMARY KEY,
+  webhook_id CHAR(36) NOT NULL,
+  event VARCHAR(64) NOT NULL,
+  payload LONGTEXT NOT NULL,                        -- imzalanan gövde, yeniden denemelerde aynen gönderilir
+  status ENUM('pending', 'success', 'failed') NOT NULL DEFAULT 'pending',
+  attempts INT NOT NULL DEFAULT 0,
+  last_status_code SMALLINT UNSIGNED NULL,
+  last_error TEXT NULL,
+  next_attempt_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  delivered_at TIMESTAMP NULL DEFAULT NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  INDEX idx_webhook_deliveries_due (status, next_attempt_at),
+  INDEX idx_webhook_deliveries_log (webhook_id, created_at),
+  FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+);
+
+-- Sadece ekleme yapılır; kayıtlar workspace veya kullanıcı silinse de korunur (bu yüzden FK yok)
+CREATE TABLE audit_events (
+  id CHAR(36) PRIMARY KEY,
+  workspace_id CHAR(36) NULL,                       -- giriş/kayıt olaylarında NULL
+  actor_id CHAR(36) NULL,
+  action VARCHAR(64) NOT NULL,                      -- page.renamed, user.login_failed, ...
+  target_type VARCHAR(32) NOT NULL,
+  target_id CHAR(36) NULL,
+  before_summary TEXT NULL,
+  after_summary TEXT NULL,
+  ip VARCHAR(45) NULL,
+  created_at TIMESTAMP(3) DEFAULT CURRENT_TIMESTAMP(3),
+  INDEX idx_audit_events_workspace (workspace_id, created_at),
+  INDEX idx_audit_events_actor (actor_id, created_at),
+  INDEX idx_audit_events_created (created_at)
+);
+
+CREATE TRIGGER audit_events_no_update BEFORE UPDATE ON audit_events
+  FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit_events is append-only';
+
+CREATE TRIGGER audit_events_no_delete BEFORE DELETE ON audit_events
+  FOR EACH ROW SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'audit_events is append-only';
+
+-- Dosyanın kendisi depolamada `storage_key` altında durur. Sayfa kalıcı silinince page_id NULL
+-- olur ve çöp temizleyici dosyayı depolamadan da siler.
+CREATE TABLE attachments (
+  id CHAR(36) PRIMARY KEY,
+  workspace_id CHAR(36) NOT NULL,
+  page_id CHAR(36) NULL,
+  storage_key VARCHAR(255) NOT NULL,
+  file_name VARCHAR(255) NOT NULL,
+  content_type VARCHAR(127) NOT NULL,
+  size_bytes BIGINT UNSIGNED NOT NULL,
+  uploaded_by CHAR(36) NULL,
+  created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  INDEX idx_attachments_page (page_id, created_at),
+  FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+  FOREIGN KEY (page_id)      REFERENCES pages(id)      ON DELETE SET NULL,
+  FOREIGN KEY (uploaded_by)  REFERENCES users(id)      ON DELETE SET NULL
+);
@@ -0,0 +1,78 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use serde_json::Value;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::config;
+
+/// İsteği yapan istemcinin IP adresi. `TRUST_PROXY_HEADERS` açıksa X-Forwarded-For'daki
+/// ilk adres, değilse TCP bağlantısının adresi kullanılır.
+#[derive(Debug, Clone)]
+pub struct ClientIp(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if config::trust_proxy_headers() {
+            let forwarded = parts
+                .headers
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty());
+            if forwarded.is_some() {
+                return Ok(ClientIp(forwarded));
+            }
+        }
+
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string());
+        Ok(ClientIp(peer))
+    }
+}
+
+/// Audit kaydı. `before`/`after` değişikliğin kısa özetidir (başlık, blok sayısı vb.),
+/// içeriğin tamamı saklanmaz.
+pub struct AuditEvent<'a> {
+    pub workspace_id: Option<&'a str>,
+    pub actor_id: Option<&'a str>,
+    pub action: &'static str,
+    pub target_type: &'static str,
+    pub target_id: Option<&'a str>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub ip: Option<&'a str>,
+}
+
+/// Olayı audit_events tablosuna ekler. Tablo sadece eklemeye açıktır; kayıt
+/// yazılamazsa asıl işlem geri alınmaz, hata loglanır.
+pub async fn record(pool: &MySqlPool, event: AuditEvent<'_>) {
+    let id = Uuid::new_v4().to_string();
+    let result = sqlx::query!(
+        "INSERT INTO audit_events (id, workspace_id, actor_id, action, target_type, target_id, before_summary, after_summary, ip)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        id,
+        event.workspace_id,
+        event.actor_id,
+        event.action,
+        event.target_type,
+        event.target_id,
+        event.before.as_ref().map(sqlx::types::Json),
+        event.after.as_ref().map(sqlx::types::Json),
+        event.ip
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!(action = event.action, error = %e, "failed to record audit event");
+    }
+}
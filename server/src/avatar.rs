@@ -0,0 +1,113 @@
+use std::fmt;
+use std::io::Cursor;
+
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+
+/// Üretilen kare avatar boyutları (piksel).
+pub const AVATAR_SIZES: [u32; 4] = [32, 64, 128, 256];
+/// `?size=` verilmediğinde sunulan boyut.
+pub const DEFAULT_AVATAR_SIZE: u32 = 128;
+/// Kabul edilen en büyük kaynak görsel kenarı; daha büyükleri açılmadan reddedilir.
+const MAX_SOURCE_DIMENSION: u32 = 8000;
+
+#[derive(Debug)]
+pub enum AvatarError {
+    UnsupportedFormat,
+    Invalid(String),
+}
+
+impl fmt::Display for AvatarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AvatarError::UnsupportedFormat => {
+                write!(f, "unsupported image format (expected PNG, JPEG, GIF or WebP)")
+            }
+            AvatarError::Invalid(e) => write!(f, "invalid image: {}", e),
+        }
+    }
+}
+
+/// Yüklenen görseli çözer, EXIF yönünü uygular, ortadan kare kırpar ve her boyut için
+/// PNG olarak yeniden kodlar. Görsel piksellerden yeniden üretildiği için EXIF/GPS
+/// dahil hiçbir üst veri çıktıya taşınmaz.
+pub fn process_avatar(data: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, AvatarError> {
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| AvatarError::Invalid(e.to_string()))?;
+    match reader.format() {
+        Some(ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP) => {}
+        _ => return Err(AvatarError::UnsupportedFormat),
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    limits.max_alloc = Some(256 * 1024 * 1024);
+    reader.limits(limits);
+
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|e| AvatarError::Invalid(e.to_string()))?;
+    // Telefon fotoğrafları çoğunlukla yan kaydedilir; yön bilgisi üst veriyle birlikte gideceği için önce uygulanır
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| AvatarError::Invalid(e.to_string()))?;
+    let mut image =
+        DynamicImage::from_decoder(decoder).map_err(|e| AvatarError::Invalid(e.to_string()))?;
+    image.apply_orientation(orientation);
+
+    let side = image.width().min(image.height());
+    if side == 0 {
+        return Err(AvatarError::Invalid("image is empty".into()));
+    }
+    let square = image.crop_imm(
+        (image.width() - side) / 2,
+        (image.height() - side) / 2,
+        side,
+        side,
+    );
+    let square = DynamicImage::ImageRgba8(square.to_rgba8());
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let resized = square.resize_exact(size, size, FilterType::Lanczos3);
+            let mut out = Cursor::new(Vec::new());
+            resized
+                .write_to(&mut out, ImageFormat::Png)
+                .map_err(|e| AvatarError::Invalid(e.to_string()))?;
+            Ok((size, out.into_inner()))
+        })
+        .collect()
+}
+
+/// İstenen boyuta en yakın üretilmiş boyut: istenenden küçük olmayan ilk boyut, yoksa en büyüğü.
+pub fn nearest_size(requested: u32) -> u32 {
+    AVATAR_SIZES
+        .iter()
+        .copied()
+        .find(|&size| size >= requested)
+        .unwrap_or(AVATAR_SIZES[AVATAR_SIZES.len() - 1])
+}
+
+pub fn storage_key(user_id: &str, version: &str, size: u32) -> String {
+    format!("avatars/{}/{}/{}.png", user_id, version, size)
+}
+
+/// `users.avatar` alanına yazılan adres; istemci `?size=` ekleyerek diğer boyutları alabilir.
+pub fn avatar_url(user_id: &str, version: &str) -> String {
+    format!("/api/avatars/{}/{}", user_id, version)
+}
+
+/// Adres bu sunucunun ürettiği bir avatar ise sürümünü döndürür.
+pub fn parse_avatar_url<'a>(url: &'a str, user_id: &str) -> Option<&'a str> {
+    let rest = url.strip_prefix("/api/avatars/")?.strip_prefix(user_id)?.strip_prefix('/')?;
+    let version = rest.split(['?', '/']).next()?;
+    is_version(version).then_some(version)
+}
+
+/// Sürümler sunucuda üretilen UUID'lerdir (tiresiz); yol parçası olarak güvenli olmaları denetlenir.
+pub fn is_version(version: &str) -> bool {
+    version.len() == 32 && version.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -0,0 +1,174 @@
+use std::env;
+
+/// Çöp kutusundaki sayfaların kalıcı olarak silinmeden önce tutulacağı gün sayısı.
+pub fn trash_retention_days() -> u32 {
+    env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30)
+}
+
+/// Sunucu bir reverse proxy arkasındaysa istemci IP'si X-Forwarded-For başlığından okunur.
+/// Doğrudan internete açık kurulumlarda başlık sahte olabileceği için varsayılan kapalıdır.
+pub fn trust_proxy_headers() -> bool {
+    env::var("TRUST_PROXY_HEADERS")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// İçe aktarma isteğinin (yüklenen dosyaların toplamı) en fazla boyutu, bayt cinsinden.
+pub fn import_max_bytes() -> usize {
+    env::var("IMPORT_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(50 * 1024 * 1024)
+}
+
+/// Tek bir eklentinin en fazla boyutu, bayt cinsinden.
+pub fn upload_max_bytes() -> usize {
+    env::var("UPLOAD_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(25 * 1024 * 1024)
+}
+
+/// Yüklenen avatar görselinin en fazla boyutu, bayt cinsinden.
+pub fn avatar_max_bytes() -> usize {
+    env::var("AVATAR_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5 * 1024 * 1024)
+}
+
+/// Yüklenmesine izin verilen içerik tipleri (virgülle ayrılmış `UPLOAD_ALLOWED_TYPES`).
+/// Tarayıcıda betik çalıştırabilecek tipler (HTML, SVG) varsayılan listede yoktur.
+pub fn upload_allowed_types() -> Vec<String> {
+    match env::var("UPLOAD_ALLOWED_TYPES") {
+        Ok(v) => v
+            .split(',')
+            .map(|t| t.trim().to_ascii_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        Err(_) => [
+            "image/png",
+            "image/jpeg",
+            "image/gif",
+            "image/webp",
+            "application/pdf",
+            "text/plain",
+            "text/csv",
+            "application/zip",
+            "application/msword",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "application/vnd.ms-excel",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ]
+        .iter()
+        .map(|t| t.to_string())
+        .collect(),
+    }
+}
+
+/// API istemcilerinin bir tarama işi için isteyebileceği en fazla sayfa sayısı.
+pub fn scrape_max_pages() -> usize {
+    env::var("SCRAPE_MAX_PAGES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(500)
+}
+
+/// Bir tarama işinin aynı anda yapabileceği en fazla istek.
+pub fn scrape_max_concurrency() -> usize {
+    env::var("SCRAPE_MAX_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10)
+}
+
+/// API'den başlatılan taramalarda aynı host'a art arda istekler arasındaki en kısa bekleme (ms).
+pub fn scrape_min_crawl_delay_ms() -> u64 {
+    env::var("SCRAPE_MIN_CRAWL_DELAY_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(500)
+}
+
+/// Bir host'a beklemeden art arda yapılabilecek en fazla istek.
+pub fn scrape_max_host_burst() -> u32 {
+    env::var("SCRAPE_MAX_HOST_BURST")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3)
+}
+
+/// Bir tarama işinin en uzun süresi (saniye); istemci daha kısasını isteyebilir.
+pub fn scrape_max_duration_secs() -> u64 {
+    env::var("SCRAPE_MAX_DURATION_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(600)
+}
+
+/// Bir adres için en fazla deneme sayısı (ilk deneme dahil).
+pub fn scrape_max_attempts() -> u32 {
+    env::var("SCRAPE_MAX_ATTEMPTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3)
+}
+
+/// Bir adresin yeniden denemelerine ve `Retry-After` beklemesine harcanabilecek en uzun süre (saniye).
+pub fn scrape_max_retry_secs() -> u64 {
+    env::var("SCRAPE_MAX_RETRY_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60)
+}
+
+/// Bir tarama işinde tek bir sayfa gövdesinin en fazla boyutu (bayt).
+pub fn scrape_max_page_bytes() -> u64 {
+    env::var("SCRAPE_MAX_PAGE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5 * 1024 * 1024)
+}
+
+/// Bir tarama işinde sırada bekleyebilecek en fazla adres.
+pub fn scrape_max_frontier() -> usize {
+    env::var("SCRAPE_MAX_FRONTIER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10_000)
+}
+
+/// Biten bir tarama işinin sonucunun (rapor dahil) bellekte tutulacağı süre (saniye).
+pub fn scrape_job_ttl_secs() -> u64 {
+    env::var("SCRAPE_JOB_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60 * 60)
+}
+
+/// Bellekte tutulan en fazla biten tarama işi; daha eskilerin sonuçları silinir.
+pub fn scrape_max_finished_jobs() -> usize {
+    env::var("SCRAPE_MAX_FINISHED_JOBS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100)
+}
+
+/// `WEBHOOK_ALLOW_PRIVATE_TARGETS=true` ise webhook'lar loopback ve özel ağ adreslerine de
+/// gönderilebilir. Sadece yerel geliştirme içindir.
+pub fn webhook_allow_private_targets() -> bool {
+    env::var("WEBHOOK_ALLOW_PRIVATE_TARGETS")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+/// `LOG_FORMAT=json` ise loglar JSON satırları olarak yazılır (log toplayıcılar için).
+/// Seviye `RUST_LOG` ile ayarlanır; varsayılan `info`.
+pub fn log_json() -> bool {
+    env::var("LOG_FORMAT")
+        .map(|v| v.eq_ignore_ascii_case("json"))
+        .unwrap_or(false)
+}
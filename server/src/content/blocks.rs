@@ -0,0 +1,397 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Sayfa içeriği: kök seviyesindeki blokların listesi.
+pub type Document = Vec<Block>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Block {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: BlockKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Block>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BlockKind {
+    Paragraph {
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    Heading {
+        level: u8,
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    BulletListItem {
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    NumberedListItem {
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    Todo {
+        #[serde(default)]
+        checked: bool,
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    Quote {
+        #[serde(default)]
+        content: Vec<Inline>,
+    },
+    Code {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+        code: String,
+    },
+    Image {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        width: Option<u32>,
+    },
+    Embed {
+        url: String,
+    },
+    Table {
+        rows: Vec<TableRow>,
+    },
+    Divider,
+    /// Başka bir sayfaya bağlantı bloğu
+    PageLink {
+        #[serde(rename = "pageId")]
+        page_id: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableRow {
+    pub cells: Vec<Vec<Inline>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Inline {
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Styles::is_plain")]
+        styles: Styles,
+    },
+    Link {
+        href: String,
+        content: Vec<Inline>,
+    },
+    /// Metin içinde sayfa bağlantısı
+    PageLink {
+        #[serde(rename = "pageId")]
+        page_id: String,
+    },
+    /// @kullanıcı bahsi
+    Mention {
+        #[serde(rename = "userId")]
+        user_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Styles {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strike: bool,
+    pub code: bool,
+}
+
+impl Styles {
+    pub fn is_plain(&self) -> bool {
+        *self == Styles::default()
+    }
+}
+
+const BLOCK_TYPES: &[&str] = &[
+    "paragraph",
+    "heading",
+    "bulletListItem",
+    "numberedListItem",
+    "todo",
+    "quote",
+    "code",
+    "image",
+    "embed",
+    "table",
+    "divider",
+    "pageLink",
+];
+
+/// Metin içeriği (`content` dizisi) taşıyan blok tipleri.
+const INLINE_BLOCK_TYPES: &[&str] = &[
+    "paragraph",
+    "heading",
+    "bulletListItem",
+    "numberedListItem",
+    "todo",
+    "quote",
+];
+
+impl BlockKind {
+    /// Metin içeriği taşıyan bloklar için satır içi öğeler.
+    pub fn inline_content(&self) -> Option<&[Inline]> {
+        match self {
+            BlockKind::Paragraph { content }
+            | BlockKind::Heading { content, .. }
+            | BlockKind::BulletListItem { content }
+            | BlockKind::NumberedListItem { content }
+            | BlockKind::Todo { content, .. }
+            | BlockKind::Quote { content } => Some(content),
+            _ => None,
+        }
+    }
+}
+
+/// Doğrulama hatası; `path` içerikteki yeri JSON Pointer olarak gösterir.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Ham JSON içeriği blok modeline göre doğrular. Hatalar ilkinde durmadan toplanır.
+pub fn validate(content: &Value) -> Result<Document, Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    let blocks = match content.as_array() {
+        Some(blocks) => blocks,
+        None => {
+            return Err(vec![ValidationError {
+                path: String::new(),
+                message: "page content must be an array of blocks".into(),
+            }])
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let document = validate_blocks(blocks, "", &mut seen, &mut errors);
+
+    if errors.is_empty() {
+        Ok(document)
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_blocks<'a>(
+    blocks: &'a [Value],
+    pointer: &str,
+    seen: &mut HashSet<&'a str>,
+    errors: &mut Vec<ValidationError>,
+) -> Document {
+    let mut document = Vec::with_capacity(blocks.len());
+    for (i, value) in blocks.iter().enumerate() {
+        let here = format!("{}/{}", pointer, i);
+        if let Some(block) = validate_block(value, &here, seen, errors) {
+            document.push(block);
+        }
+    }
+    document
+}
+
+fn validate_block<'a>(
+    value: &'a Value,
+    pointer: &str,
+    seen: &mut HashSet<&'a str>,
+    errors: &mut Vec<ValidationError>,
+) -> Option<Block> {
+    let mut error = |path: String, message: String| errors.push(ValidationError { path, message });
+
+    let obj = match value.as_object() {
+        Some(obj) => obj,
+        None => {
+            error(pointer.to_string(), "block must be an object".into());
+            return None;
+        }
+    };
+
+    let id = match obj.get("id").and_then(Value::as_str) {
+        Some(id) if !id.is_empty() => {
+            if !seen.insert(id) {
+                error(format!("{}/id", pointer), format!("duplicate block id `{}`", id));
+            }
+            Some(id)
+        }
+        _ => {
+            error(format!("{}/id", pointer), "block id must be a non-empty string".into());
+            None
+        }
+    };
+
+    let block_type = match obj.get("type").and_then(Value::as_str) {
+        Some(t) if BLOCK_TYPES.contains(&t) => Some(t),
+        Some(t) => {
+            error(format!("{}/type", pointer), format!("unknown block type `{}`", t));
+            None
+        }
+        None => {
+            error(format!("{}/type", pointer), "block type must be a string".into());
+            None
+        }
+    };
+
+    // Metin içeriğindeki hataları tek tek işaretle
+    let mut inline_ok = true;
+    if let (Some(t), Some(content)) = (block_type, obj.get("content")) {
+        if INLINE_BLOCK_TYPES.contains(&t) {
+            match content.as_array() {
+                Some(items) => {
+                    for (j, item) in items.iter().enumerate() {
+                        if let Err(e) = serde_json::from_value::<Inline>(item.clone()) {
+                            inline_ok = false;
+                            error(format!("{}/content/{}", pointer, j), e.to_string());
+                        }
+                    }
+                }
+                None => {
+                    inline_ok = false;
+                    error(format!("{}/content", pointer), "content must be an array".into());
+                }
+            }
+        }
+    }
+
+    let kind = match block_type {
+        Some(_) if inline_ok => {
+            let mut fields = obj.clone();
+            fields.remove("id");
+            fields.remove("children");
+            match serde_json::from_value::<BlockKind>(Value::Object(fields)) {
+                Ok(kind) => Some(kind),
+                Err(e) => {
+                    error(pointer.to_string(), e.to_string());
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    if let Some(BlockKind::Heading { level, .. }) = &kind {
+        if !(1..=6).contains(level) {
+            error(format!("{}/level", pointer), "heading level must be between 1 and 6".into());
+        }
+    }
+
+    let children = match obj.get("children") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(children)) => {
+            validate_blocks(children, &format!("{}/children", pointer), seen, errors)
+        }
+        Some(_) => {
+            errors.push(ValidationError {
+                path: format!("{}/children", pointer),
+                message: "children must be an array".into(),
+            });
+            Vec::new()
+        }
+    };
+
+    Some(Block {
+        id: id?.to_string(),
+        kind: kind?,
+        children,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn paths(content: Value) -> Vec<String> {
+        validate(&content)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.path)
+            .collect()
+    }
+
+    #[test]
+    fn valid_document() {
+        let document = validate(&json!([
+            {
+                "id": "a",
+                "type": "heading",
+                "level": 2,
+                "content": [{ "type": "text", "text": "Title", "styles": { "bold": true } }],
+            },
+            {
+                "id": "b",
+                "type": "bulletListItem",
+                "content": [{ "type": "link", "href": "https://example.com", "content": [] }],
+                "children": [{ "id": "c", "type": "pageLink", "pageId": "p1" }],
+            },
+            { "id": "d", "type": "divider", "children": null },
+        ]))
+        .unwrap();
+
+        assert_eq!(document.len(), 3);
+        assert!(matches!(document[0].kind, BlockKind::Heading { level: 2, .. }));
+        assert_eq!(
+            document[1].children[0].kind,
+            BlockKind::PageLink { page_id: "p1".into() }
+        );
+        assert_eq!(document[2].kind, BlockKind::Divider);
+        assert_eq!(document[1].kind.inline_content().map(<[Inline]>::len), Some(1));
+    }
+
+    #[test]
+    fn content_must_be_an_array() {
+        assert_eq!(paths(json!({ "blocks": [] })), vec![""]);
+        assert!(validate(&json!([])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_field() {
+        let errors = paths(json!([
+            { "id": "a", "type": "paragraph", "content": [{ "type": "text", "text": "ok" }, { "type": "bogus" }] },
+            { "id": "a", "type": "nope" },
+            { "type": "heading", "level": 9, "content": [] },
+            "text",
+            { "id": "e", "type": "quote", "children": [{ "id": "f", "type": "todo", "content": "x" }] },
+            { "id": "g", "type": "divider", "children": {} },
+            { "id": "h", "type": "image" },
+        ]));
+        assert_eq!(
+            errors,
+            vec![
+                "/0/content/1",
+                "/1/id",
+                "/1/type",
+                "/2/id",
+                "/2/level",
+                "/3",
+                "/4/children/0/content",
+                "/5/children",
+                "/6",
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_ids_are_found_across_levels() {
+        let errors = validate(&json!([
+            { "id": "a", "type": "paragraph", "children": [{ "id": "b", "type": "paragraph" }] },
+            { "id": "b", "type": "paragraph" },
+        ]))
+        .unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "/1/id");
+        assert_eq!(errors[0].message, "duplicate block id `b`");
+    }
+}
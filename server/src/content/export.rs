@@ -0,0 +1,666 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::content::blocks::{self, Block, BlockKind, Document, Inline, Styles, TableRow};
+use crate::content::search::plain_text;
+
+/// Dışa aktarma biçimi; `?format=` parametresinden okunur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[serde(alias = "md")]
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+            ExportFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn render(&self, title: &str, document: &[Block], links: &LinkContext) -> String {
+        match self {
+            ExportFormat::Markdown => to_markdown(title, document, links),
+            ExportFormat::Html => to_html(title, document, links),
+        }
+    }
+}
+
+/// Sayfa ve kullanıcı bağlantılarının çözümlenmesi. Yolu olmayan sayfalar sadece başlık
+/// olarak yazılır (ör. tek sayfa dışa aktarımında diğer sayfalar). `base` verilmişse
+/// yollar o dosyaya göreli bağlantıya çevrilir.
+#[derive(Debug, Default)]
+pub struct LinkContext {
+    pages: HashMap<String, (String, Option<String>)>,
+    users: HashMap<String, String>,
+    base: Option<String>,
+}
+
+impl LinkContext {
+    pub fn add_page(&mut self, page_id: &str, title: &str, path: Option<String>) {
+        self.pages.insert(page_id.to_string(), (title.to_string(), path));
+    }
+
+    pub fn add_user(&mut self, user_id: &str, username: &str) {
+        self.users.insert(user_id.to_string(), username.to_string());
+    }
+
+    /// Bağlantıların yazılacağı dosyanın arşivdeki yolu
+    pub fn set_base(&mut self, path: &str) {
+        self.base = Some(path.to_string());
+    }
+
+    fn page(&self, page_id: &str) -> (&str, Option<String>) {
+        match self.pages.get(page_id) {
+            Some((title, Some(path))) => {
+                let href = match &self.base {
+                    Some(base) => relative_href(base, path),
+                    None => path.clone(),
+                };
+                (title, Some(href))
+            }
+            Some((title, None)) => (title, None),
+            None => ("Missing page", None),
+        }
+    }
+
+    fn username(&self, user_id: &str) -> &str {
+        self.users.get(user_id).map(String::as_str).unwrap_or("unknown")
+    }
+}
+
+/// Kayıtlı içeriği dışa aktarma için blok modeline çevirir. Doğrulanamayan eski içerik
+/// kaybolmasın diye düz metni paragraflar halinde döner.
+pub fn export_document(content: Option<&Value>) -> Document {
+    let Some(content) = content else {
+        return Vec::new();
+    };
+    match blocks::validate(content) {
+        Ok(document) => document,
+        Err(_) => plain_text(content)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| Block {
+                id: format!("export-{}", i),
+                kind: BlockKind::Paragraph {
+                    content: vec![Inline::Text {
+                        text: line.to_string(),
+                        styles: Styles::default(),
+                    }],
+                },
+                children: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+// --- Markdown ---
+
+pub fn to_markdown(title: &str, document: &[Block], links: &LinkContext) -> String {
+    let mut out = format!("# {}\n\n", escape_markdown(title));
+    markdown_blocks(document, "", links, &mut out);
+    while out.ends_with("\n\n") {
+        out.pop();
+    }
+    out
+}
+
+fn markdown_blocks(blocks: &[Block], indent: &str, links: &LinkContext, out: &mut String) {
+    for (i, block) in blocks.iter().enumerate() {
+        let next_is_list = blocks.get(i + 1).is_some_and(|b| is_list_item(&b.kind));
+        markdown_block(block, indent, links, out);
+        // Liste öğeleri arasında boş satır bırakılmaz, aksi halde liste "gevşek" olur
+        if !(is_list_item(&block.kind) && next_is_list) {
+            out.push('\n');
+        }
+    }
+}
+
+fn is_list_item(kind: &BlockKind) -> bool {
+    matches!(
+        kind,
+        BlockKind::BulletListItem { .. } | BlockKind::NumberedListItem { .. } | BlockKind::Todo { .. }
+    )
+}
+
+fn markdown_block(block: &Block, indent: &str, links: &LinkContext, out: &mut String) {
+    let mut line = |text: &str| {
+        out.push_str(indent);
+        out.push_str(text);
+        out.push('\n');
+    };
+
+    // Liste öğelerinin alt blokları işaret genişliği kadar içeri alınır
+    let child_indent = match &block.kind {
+        BlockKind::BulletListItem { content } => {
+            line(&format!("- {}", markdown_inline(content, links)));
+            format!("{}  ", indent)
+        }
+        BlockKind::NumberedListItem { content } => {
+            line(&format!("1. {}", markdown_inline(content, links)));
+            format!("{}   ", indent)
+        }
+        BlockKind::Todo { checked, content } => {
+            let mark = if *checked { "x" } else { " " };
+            line(&format!("- [{}] {}", mark, markdown_inline(content, links)));
+            format!("{}  ", indent)
+        }
+        BlockKind::Paragraph { content } => {
+            line(&markdown_inline(content, links));
+            indent.to_string()
+        }
+        BlockKind::Heading { level, content } => {
+            // Sayfa başlığı H1 olduğu için içerikteki başlıklar bir seviye aşağı kaydırılır
+            let hashes = "#".repeat((*level as usize + 1).min(6));
+            line(&format!("{} {}", hashes, markdown_inline(content, links)));
+            indent.to_string()
+        }
+        BlockKind::Quote { content } => {
+            for text in markdown_inline(content, links).split('\n') {
+                line(&format!("> {}", text));
+            }
+            indent.to_string()
+        }
+        BlockKind::Code { language, code } => {
+            let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+            line(&format!("{}{}", fence, language.as_deref().unwrap_or("")));
+            for text in code.lines() {
+                line(text);
+            }
+            line(&fence);
+            indent.to_string()
+        }
+        BlockKind::Image { url, caption, .. } => {
+            line(&format!(
+                "![{}]({})",
+                escape_markdown(caption.as_deref().unwrap_or("")),
+                markdown_url(url)
+            ));
+            indent.to_string()
+        }
+        BlockKind::Embed { url } if safe_href(url) => {
+            line(&format!("<{}>", url.replace('>', "%3E")));
+            indent.to_string()
+        }
+        BlockKind::Embed { url } => {
+            line(&escape_markdown(url));
+            indent.to_string()
+        }
+        BlockKind::Table { rows } => {
+            for text in markdown_table(rows, links) {
+                line(&text);
+            }
+            indent.to_string()
+        }
+        BlockKind::Divider => {
+            line("---");
+            indent.to_string()
+        }
+        BlockKind::PageLink { page_id } => {
+            line(&markdown_page_link(page_id, links));
+            indent.to_string()
+        }
+    };
+
+    if !block.children.is_empty() {
+        if !is_list_item(&block.kind) {
+            out.push('\n');
+        }
+        markdown_blocks(&block.children, &child_indent, links, out);
+        // Alt blokların son boş satırı üst seviyenin ayırıcısıyla birleşir
+        if out.ends_with("\n\n") {
+            out.pop();
+        }
+    }
+}
+
+fn markdown_table(rows: &[TableRow], links: &LinkContext) -> Vec<String> {
+    let width = rows.iter().map(|r| r.cells.len()).max().unwrap_or(0);
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let row_line = |row: &TableRow| {
+        let cells: Vec<String> = (0..width)
+            .map(|i| {
+                row.cells
+                    .get(i)
+                    .map(|cell| markdown_inline(cell, links).replace('\n', "<br>"))
+                    .unwrap_or_default()
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    // İlk satır başlık kabul edilir
+    let mut lines = vec![row_line(&rows[0]), format!("|{}", " --- |".repeat(width))];
+    lines.extend(rows[1..].iter().map(row_line));
+    lines
+}
+
+fn markdown_inline(items: &[Inline], links: &LinkContext) -> String {
+    let mut out = String::new();
+    for item in items {
+        match item {
+            Inline::Text { text, styles } => out.push_str(&markdown_styled(text, styles)),
+            Inline::Link { href, content } if safe_href(href) => {
+                out.push_str(&format!("[{}]({})", markdown_inline(content, links), markdown_url(href)));
+            }
+            Inline::Link { content, .. } => out.push_str(&markdown_inline(content, links)),
+            Inline::PageLink { page_id } => out.push_str(&markdown_page_link(page_id, links)),
+            Inline::Mention { user_id } => {
+                out.push('@');
+                out.push_str(&escape_markdown(links.username(user_id)));
+            }
+        }
+    }
+    out
+}
+
+fn markdown_styled(text: &str, styles: &Styles) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let mut out = if styles.code {
+        let ticks = "`".repeat(longest_run(text, '`') + 1);
+        let pad = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+        format!("{}{}{}{}{}", ticks, pad, text, pad, ticks)
+    } else {
+        escape_markdown(text)
+    };
+
+    // Baştaki/sondaki boşluklar işaretlerin dışında kalmalı, yoksa vurgu tanınmaz
+    let trimmed = out.trim();
+    if trimmed.is_empty() {
+        return out;
+    }
+    let leading = &out[..out.len() - out.trim_start().len()];
+    let trailing = &out[out.trim_end().len()..];
+    let mut core = trimmed.to_string();
+    if styles.bold {
+        core = format!("**{}**", core);
+    }
+    if styles.italic {
+        core = format!("*{}*", core);
+    }
+    if styles.strike {
+        core = format!("~~{}~~", core);
+    }
+    if styles.underline {
+        core = format!("<u>{}</u>", core);
+    }
+    out = format!("{}{}{}", leading, core, trailing);
+    out
+}
+
+fn markdown_page_link(page_id: &str, links: &LinkContext) -> String {
+    let (title, href) = links.page(page_id);
+    match href {
+        Some(href) => format!("[{}]({})", escape_markdown(title), href),
+        None => escape_markdown(title),
+    }
+}
+
+fn markdown_url(url: &str) -> String {
+    url.replace(' ', "%20").replace('(', "%28").replace(')', "%29")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' => {
+                out.push('\\');
+                out.push(c);
+            }
+            // Satır başında blok sözdizimi sayılabilecek karakterler
+            '#' | '-' | '+' if i == 0 => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn longest_run(text: &str, target: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == target {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+// --- HTML ---
+
+const HTML_STYLE: &str = "body{max-width:760px;margin:2rem auto;padding:0 1rem;font-family:system-ui,sans-serif;line-height:1.6;color:#1f2328}\
+pre{background:#f6f8fa;padding:1rem;overflow:auto}code{font-family:ui-monospace,monospace}\
+blockquote{margin:0;padding-left:1rem;border-left:3px solid #d0d7de;color:#57606a}\
+table{border-collapse:collapse}td,th{border:1px solid #d0d7de;padding:.3rem .6rem}\
+ul.todo{list-style:none;padding-left:1.2rem}img{max-width:100%}figcaption{color:#57606a;font-size:.9em}";
+
+/// Tek başına açılabilen HTML belgesi; dış kaynak (CSS/JS) kullanılmaz.
+pub fn to_html(title: &str, document: &[Block], links: &LinkContext) -> String {
+    let mut body = String::new();
+    html_blocks(document, links, &mut body);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{body}</body>\n</html>\n",
+        title = escape_html(title),
+        style = HTML_STYLE,
+        body = body
+    )
+}
+
+#[derive(PartialEq)]
+enum ListKind {
+    Bullet,
+    Numbered,
+    Todo,
+}
+
+fn list_kind(kind: &BlockKind) -> Option<ListKind> {
+    match kind {
+        BlockKind::BulletListItem { .. } => Some(ListKind::Bullet),
+        BlockKind::NumberedListItem { .. } => Some(ListKind::Numbered),
+        BlockKind::Todo { .. } => Some(ListKind::Todo),
+        _ => None,
+    }
+}
+
+fn html_blocks(blocks: &[Block], links: &LinkContext, out: &mut String) {
+    let mut i = 0;
+    while i < blocks.len() {
+        let Some(kind) = list_kind(&blocks[i].kind) else {
+            html_block(&blocks[i], links, out);
+            i += 1;
+            continue;
+        };
+
+        // Ardışık aynı tür liste öğeleri tek listede toplanır
+        let (open, close) = match kind {
+            ListKind::Bullet => ("<ul>", "</ul>"),
+            ListKind::Numbered => ("<ol>", "</ol>"),
+            ListKind::Todo => ("<ul class=\"todo\">", "</ul>"),
+        };
+        out.push_str(open);
+        out.push('\n');
+        while i < blocks.len() && list_kind(&blocks[i].kind).as_ref() == Some(&kind) {
+            html_block(&blocks[i], links, out);
+            i += 1;
+        }
+        out.push_str(close);
+        out.push('\n');
+    }
+}
+
+fn html_block(block: &Block, links: &LinkContext, out: &mut String) {
+    let children = |out: &mut String| {
+        if !block.children.is_empty() {
+            html_blocks(&block.children, links, out);
+        }
+    };
+
+    match &block.kind {
+        BlockKind::BulletListItem { content } | BlockKind::NumberedListItem { content } => {
+            out.push_str(&format!("<li>{}", html_inline(content, links)));
+            children(out);
+            out.push_str("</li>\n");
+        }
+        BlockKind::Todo { checked, content } => {
+            let checked = if *checked { " checked" } else { "" };
+            out.push_str(&format!(
+                "<li><input type=\"checkbox\" disabled{}> {}",
+                checked,
+                html_inline(content, links)
+            ));
+            children(out);
+            out.push_str("</li>\n");
+        }
+        BlockKind::Paragraph { content } => {
+            out.push_str(&format!("<p>{}</p>\n", html_inline(content, links)));
+            children(out);
+        }
+        BlockKind::Heading { level, content } => {
+            let level = (*level + 1).min(6);
+            out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, html_inline(content, links)));
+            children(out);
+        }
+        BlockKind::Quote { content } => {
+            out.push_str(&format!("<blockquote><p>{}</p>", html_inline(content, links)));
+            children(out);
+            out.push_str("</blockquote>\n");
+        }
+        BlockKind::Code { language, code } => {
+            let class = language
+                .as_deref()
+                .map(|l| format!(" class=\"language-{}\"", escape_html(l)))
+                .unwrap_or_default();
+            out.push_str(&format!("<pre><code{}>{}</code></pre>\n", class, escape_html(code)));
+            children(out);
+        }
+        BlockKind::Image { url, caption, width } if safe_href(url) => {
+            let width = width.map(|w| format!(" width=\"{}\"", w)).unwrap_or_default();
+            let alt = escape_html(caption.as_deref().unwrap_or(""));
+            out.push_str(&format!(
+                "<figure><img src=\"{}\" alt=\"{}\"{}>",
+                escape_html(url),
+                alt,
+                width
+            ));
+            if caption.is_some() {
+                out.push_str(&format!("<figcaption>{}</figcaption>", alt));
+            }
+            out.push_str("</figure>\n");
+            children(out);
+        }
+        BlockKind::Image { caption, .. } => {
+            if let Some(caption) = caption {
+                out.push_str(&format!("<p>{}</p>\n", escape_html(caption)));
+            }
+            children(out);
+        }
+        BlockKind::Embed { url } => {
+            if safe_href(url) {
+                out.push_str(&format!("<p><a href=\"{0}\">{0}</a></p>\n", escape_html(url)));
+            } else {
+                out.push_str(&format!("<p>{}</p>\n", escape_html(url)));
+            }
+            children(out);
+        }
+        BlockKind::Table { rows } => {
+            out.push_str("<table>\n");
+            for (i, row) in rows.iter().enumerate() {
+                let tag = if i == 0 { "th" } else { "td" };
+                out.push_str("<tr>");
+                for cell in &row.cells {
+                    out.push_str(&format!("<{0}>{1}</{0}>", tag, html_inline(cell, links)));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</table>\n");
+            children(out);
+        }
+        BlockKind::Divider => {
+            out.push_str("<hr>\n");
+            children(out);
+        }
+        BlockKind::PageLink { page_id } => {
+            out.push_str(&format!("<p>{}</p>\n", html_page_link(page_id, links)));
+            children(out);
+        }
+    }
+}
+
+fn html_inline(items: &[Inline], links: &LinkContext) -> String {
+    let mut out = String::new();
+    for item in items {
+        match item {
+            Inline::Text { text, styles } => {
+                let mut html = escape_html(text).replace('\n', "<br>");
+                for (on, tag) in [
+                    (styles.code, "code"),
+                    (styles.bold, "strong"),
+                    (styles.italic, "em"),
+                    (styles.underline, "u"),
+                    (styles.strike, "s"),
+                ] {
+                    if on {
+                        html = format!("<{0}>{1}</{0}>", tag, html);
+                    }
+                }
+                out.push_str(&html);
+            }
+            Inline::Link { href, content } if safe_href(href) => {
+                out.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(href),
+                    html_inline(content, links)
+                ));
+            }
+            Inline::Link { content, .. } => out.push_str(&html_inline(content, links)),
+            Inline::PageLink { page_id } => out.push_str(&html_page_link(page_id, links)),
+            Inline::Mention { user_id } => {
+                out.push_str(&format!(
+                    "<span class=\"mention\">@{}</span>",
+                    escape_html(links.username(user_id))
+                ));
+            }
+        }
+    }
+    out
+}
+
+fn html_page_link(page_id: &str, links: &LinkContext) -> String {
+    let (title, href) = links.page(page_id);
+    match href {
+        Some(href) => format!("<a href=\"{}\">{}</a>", escape_html(&href), escape_html(title)),
+        None => escape_html(title),
+    }
+}
+
+/// Dışa aktarılan dosyada tıklanabilir bırakılacak adresler: http(s), mailto ve göreli yollar.
+/// `javascript:` gibi diğer şemalar düz metin olarak yazılır.
+fn safe_href(url: &str) -> bool {
+    // Tarayıcılar şemayı ararken baştaki boşlukları, sekme ve satır sonlarını yok sayar
+    let url: String = url
+        .trim_start_matches(|c: char| c.is_ascii_whitespace() || c.is_ascii_control())
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let end = url.find(['/', '?', '#']).unwrap_or(url.len());
+    match url[..end].find(':') {
+        None => true,
+        Some(i) => {
+            let scheme = url[..i].to_ascii_lowercase();
+            matches!(scheme.as_str(), "http" | "https" | "mailto")
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// --- Workspace arşivi ---
+
+/// Arşivdeki dosya yolları: her sayfa `Başlık.md`, alt sayfaları `Başlık/` klasöründe.
+/// Aynı klasörde aynı başlık varsa ` (2)`, ` (3)` eklenir. Sayfalar ebeveynden önce gelmelidir.
+pub fn page_paths(
+    pages: &[(String, String, Option<String>)],
+    format: ExportFormat,
+) -> HashMap<String, String> {
+    let mut dirs: HashMap<String, String> = HashMap::new();
+    let mut paths: HashMap<String, String> = HashMap::new();
+    let mut taken: HashMap<String, u32> = HashMap::new();
+
+    for (id, title, parent_id) in pages {
+        // Ebeveyni arşivde olmayan sayfalar köke yerleşir
+        let dir = parent_id
+            .as_ref()
+            .and_then(|p| dirs.get(p))
+            .cloned()
+            .unwrap_or_default();
+
+        let base = sanitize_file_name(title);
+        let mut name = base.clone();
+        let mut n = 1;
+        while taken.contains_key(&format!("{}{}", dir, name.to_lowercase())) {
+            n += 1;
+            name = format!("{} ({})", base, n);
+        }
+        taken.insert(format!("{}{}", dir, name.to_lowercase()), n);
+
+        paths.insert(id.clone(), format!("{}{}.{}", dir, name, format.extension()));
+        dirs.insert(id.clone(), format!("{}{}/", dir, name));
+    }
+    paths
+}
+
+/// `from` dosyasından `to` dosyasına göreli bağlantı, URL olarak kodlanmış.
+pub fn relative_href(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = from.split('/').collect();
+    let from_dirs = &from_dirs[..from_dirs.len() - 1];
+    let to_parts: Vec<&str> = to.split('/').collect();
+
+    let common = from_dirs
+        .iter()
+        .zip(&to_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from_dirs.len() - common];
+    parts.extend(to_parts[common..].iter().map(|p| encode_path_segment(p)));
+    parts.join("/")
+}
+
+pub fn sanitize_file_name(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').trim();
+    let cleaned: String = cleaned.chars().take(100).collect();
+    if cleaned.is_empty() {
+        "Untitled".to_string()
+    } else {
+        cleaned
+    }
+}
+
+pub fn encode_path_segment(segment: &str) -> String {
+    let mut out = String::new();
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => out.push(b as char),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
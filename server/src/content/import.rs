@@ -0,0 +1,1180 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Cursor, Read};
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use scraper::{ElementRef, Html, Node, Selector};
+use uuid::Uuid;
+use zip::ZipArchive;
+
+use crate::content::blocks::{Block, BlockKind, Document, Inline, Styles, TableRow};
+
+/// Bir içe aktarma isteğinde okunacak en fazla sayfa dosyası.
+pub const MAX_FILES: usize = 2000;
+/// Bir içe aktarma isteğindeki dosyaların açılmış toplam boyut sınırı; sıkıştırma bombalarına karşı.
+pub const MAX_UNCOMPRESSED_BYTES: u64 = 200 * 1024 * 1024;
+/// Dönüştürülen belgede blokların en fazla iç içe geçme seviyesi. Daha derin içerik en yakın
+/// izin verilen ebeveyne düzleştirilir; çok derin belgeler kaydedildikten sonra geri okunamaz.
+const MAX_BLOCK_DEPTH: usize = 32;
+/// HTML dönüştürülürken izlenen en fazla öğe iç içeliği; daha derindeki öğelerin sadece metni alınır.
+const MAX_HTML_NESTING: usize = 256;
+
+#[derive(Debug)]
+pub enum ImportError {
+    Archive(String),
+    TooManyFiles,
+    TooLarge,
+    Unsupported(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Archive(e) => write!(f, "invalid ZIP archive: {}", e),
+            ImportError::TooManyFiles => {
+                write!(f, "import contains more than {} pages", MAX_FILES)
+            }
+            ImportError::TooLarge => write!(f, "import is too large when uncompressed"),
+            ImportError::Unsupported(name) => write!(
+                f,
+                "unsupported file type: {} (expected .md, .html or .zip)",
+                name
+            ),
+        }
+    }
+}
+
+/// Yüklenen ya da arşivden çıkarılan tek dosya; `path` arşiv içindeki göreli yoldur.
+pub struct ImportFile {
+    pub path: String,
+    pub data: Vec<u8>,
+}
+
+/// İçe aktarılacak sayfa. `parent` aynı listedeki ebeveynin indeksidir ve her zaman
+/// sayfanın kendisinden önce gelir.
+pub struct ImportedPage {
+    pub title: String,
+    pub parent: Option<usize>,
+    pub document: Document,
+    /// Uzantısız arşiv yolu; göreli bağlantılar buna göre çözülür
+    key: String,
+    /// Klasörden üretilen sayfalarda dosya yoktur
+    source: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SourceFormat {
+    Markdown,
+    Html,
+}
+
+fn source_format(path: &str) -> Option<SourceFormat> {
+    match extension(path).as_str() {
+        "md" | "markdown" => Some(SourceFormat::Markdown),
+        "html" | "htm" => Some(SourceFormat::Html),
+        _ => None,
+    }
+}
+
+fn extension(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext.to_ascii_lowercase(),
+        _ => String::new(),
+    }
+}
+
+pub fn is_zip(file_name: &str) -> bool {
+    extension(file_name) == "zip"
+}
+
+/// Bir içe aktarma isteğindeki bütün yüklemelerden toplanan sayfa dosyaları. Dosya sayısı ve
+/// açılmış boyut sınırları her yükleme için ayrı değil, istek boyunca birlikte sayılır.
+#[derive(Default)]
+pub struct ImportFiles {
+    files: Vec<ImportFile>,
+    total: u64,
+}
+
+impl ImportFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tek başına yüklenen bir .md/.html dosyasını ekler; desteklenmeyen tiplerde hata döndürür.
+    pub fn add_file(&mut self, file_name: &str, data: Vec<u8>) -> Result<(), ImportError> {
+        if source_format(file_name).is_none() {
+            return Err(ImportError::Unsupported(file_name.to_string()));
+        }
+        // Ayrı ayrı yüklenen dosyalar klasör yolu taşımaz
+        let path = file_name.rsplit(['/', '\\']).next().unwrap_or("").to_string();
+        self.count(data.len() as u64)?;
+        self.push(ImportFile { path, data })
+    }
+
+    /// ZIP içindeki Markdown/HTML dosyalarını ekler. Notion'ın büyük dışa aktarımlarda yaptığı
+    /// gibi arşivin içinde başka ZIP'ler varsa bunlar da (bir seviye) açılır; diğer dosyalar atlanır.
+    pub fn add_zip(&mut self, data: &[u8]) -> Result<(), ImportError> {
+        self.read_zip(data, true)
+    }
+
+    pub fn into_files(self) -> Vec<ImportFile> {
+        self.files
+    }
+
+    fn count(&mut self, bytes: u64) -> Result<(), ImportError> {
+        self.total += bytes;
+        if self.total > MAX_UNCOMPRESSED_BYTES {
+            return Err(ImportError::TooLarge);
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, file: ImportFile) -> Result<(), ImportError> {
+        if self.files.len() >= MAX_FILES {
+            return Err(ImportError::TooManyFiles);
+        }
+        self.files.push(file);
+        Ok(())
+    }
+
+    fn read_zip(&mut self, data: &[u8], nested: bool) -> Result<(), ImportError> {
+        let mut archive =
+            ZipArchive::new(Cursor::new(data)).map_err(|e| ImportError::Archive(e.to_string()))?;
+
+        for i in 0..archive.len() {
+            let mut entry = archive
+                .by_index(i)
+                .map_err(|e| ImportError::Archive(e.to_string()))?;
+            if entry.is_dir() {
+                continue;
+            }
+            // Arşiv dışına çıkan (../) ya da mutlak yollar atlanır
+            let Some(path) = entry.enclosed_name() else {
+                continue;
+            };
+            let path = path.to_string_lossy().replace('\\', "/");
+            if path.starts_with("__MACOSX/") || path.split('/').any(|s| s.starts_with('.')) {
+                continue;
+            }
+
+            let zip = is_zip(&path);
+            if !zip && source_format(&path).is_none() {
+                continue;
+            }
+            if zip && !nested {
+                continue;
+            }
+
+            let remaining = MAX_UNCOMPRESSED_BYTES.saturating_sub(self.total);
+            if entry.size() > remaining {
+                return Err(ImportError::TooLarge);
+            }
+            // Başlıktaki boyuta güvenilmez, okunan miktar da sınırlanır
+            let mut buf = Vec::new();
+            entry
+                .by_ref()
+                .take(remaining + 1)
+                .read_to_end(&mut buf)
+                .map_err(|e| ImportError::Archive(e.to_string()))?;
+            self.count(buf.len() as u64)?;
+
+            if zip {
+                self.read_zip(&buf, false)?;
+                continue;
+            }
+            self.push(ImportFile { path, data: buf })?;
+        }
+        Ok(())
+    }
+}
+
+/// Dosyaları sayfalara çevirir ve klasör yapısını sayfa ağacı olarak kurar.
+/// `Sayfa.md` ile yanındaki `Sayfa/` klasörü aynı sayfadır (Notion düzeni); karşılığı
+/// olmayan klasörler için boş bir sayfa oluşturulur.
+pub fn build_pages(mut files: Vec<ImportFile>) -> Vec<ImportedPage> {
+    files.retain(|f| source_format(&f.path).is_some());
+    strip_common_root(&mut files);
+
+    // Ebeveyn dosyaları çocuklarından önce işlensin diye önce derinliğe göre sırala
+    files.sort_by(|a, b| {
+        let depth = |p: &str| p.matches('/').count();
+        depth(&a.path)
+            .cmp(&depth(&b.path))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let mut pages: Vec<ImportedPage> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+
+    for file in files {
+        let key = strip_extension(&file.path).to_string();
+        if by_key.contains_key(&key) {
+            // Aynı sayfanın hem .md hem .html hali varsa ilki kullanılır
+            continue;
+        }
+
+        let text = String::from_utf8_lossy(&file.data);
+        let text = text.trim_start_matches('\u{feff}');
+        let (title, document) = match source_format(&file.path) {
+            Some(SourceFormat::Html) => html_to_document(text),
+            _ => markdown_to_document(text),
+        };
+
+        let parent = key
+            .rsplit_once('/')
+            .map(|(dir, _)| ensure_folder(dir, &mut pages, &mut by_key));
+        let title = title.unwrap_or_else(|| display_name(key.rsplit('/').next().unwrap_or(&key)));
+
+        by_key.insert(key.clone(), pages.len());
+        pages.push(ImportedPage {
+            title,
+            parent,
+            document,
+            key,
+            source: Some(file.path),
+        });
+    }
+    pages
+}
+
+fn ensure_folder(
+    dir: &str,
+    pages: &mut Vec<ImportedPage>,
+    by_key: &mut HashMap<String, usize>,
+) -> usize {
+    if let Some(&index) = by_key.get(dir) {
+        return index;
+    }
+    let parent = dir
+        .rsplit_once('/')
+        .map(|(up, _)| ensure_folder(up, pages, by_key));
+    by_key.insert(dir.to_string(), pages.len());
+    pages.push(ImportedPage {
+        title: display_name(dir.rsplit('/').next().unwrap_or(dir)),
+        parent,
+        document: Vec::new(),
+        key: dir.to_string(),
+        source: None,
+    });
+    pages.len() - 1
+}
+
+/// Bütün dosyalar tek bir klasörün altındaysa ve bu klasörün kendi sayfası yoksa
+/// klasör atlanır; "Export/" gibi sarmalayıcı klasörler gereksiz bir sayfa oluşturmasın.
+fn strip_common_root(files: &mut [ImportFile]) {
+    let Some(first) = files.first() else {
+        return;
+    };
+    let Some((root, _)) = first.path.split_once('/') else {
+        return;
+    };
+    let prefix = format!("{}/", root);
+    let shared = files.iter().all(|f| f.path.starts_with(&prefix));
+    let own_page = files.iter().any(|f| strip_extension(&f.path) == root);
+    if shared && !own_page {
+        for file in files.iter_mut() {
+            file.path = file.path[prefix.len()..].to_string();
+        }
+    }
+}
+
+fn strip_extension(path: &str) -> &str {
+    match path.rsplit_once('.') {
+        Some((stem, _)) if source_format(path).is_some() => stem,
+        _ => path,
+    }
+}
+
+/// Notion dosya adlarının sonuna eklenen " 0123…cdef" (32 hex) kimliğini atar.
+fn display_name(segment: &str) -> String {
+    let name = match segment.rsplit_once(' ') {
+        Some((name, id))
+            if !name.is_empty() && id.len() == 32 && id.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            name
+        }
+        _ => segment,
+    };
+    match name.trim() {
+        "" => "Untitled".to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// Arşiv içindeki göreli bağlantıları sayfa bağlantısına çevirir. `ids`, `pages` ile aynı
+/// sırada oluşturulan sayfa kimlikleridir.
+pub fn link_pages(pages: &mut [ImportedPage], ids: &[String]) {
+    let by_key: HashMap<String, String> = pages
+        .iter()
+        .zip(ids)
+        .map(|(page, id)| (page.key.clone(), id.clone()))
+        .collect();
+
+    for page in pages.iter_mut() {
+        let Some(source) = &page.source else {
+            continue;
+        };
+        let dir = source.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+        let resolve = |href: &str| resolve_href(href, dir).and_then(|key| by_key.get(&key).cloned());
+        link_blocks(&mut page.document, &resolve);
+    }
+}
+
+fn resolve_href(href: &str, dir: &str) -> Option<String> {
+    if href.contains("://") || href.starts_with("mailto:") || href.starts_with('#') || href.starts_with('/') {
+        return None;
+    }
+    let path = percent_decode(href.split(['#', '?']).next().unwrap_or(""));
+    source_format(&path)?;
+
+    let mut segments: Vec<&str> = dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(strip_extension(&segments.join("/")).to_string())
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            let hex = |b: u8| (b as char).to_digit(16).unwrap_or(0) as u8;
+            out.push(hex(bytes[i + 1]) * 16 + hex(bytes[i + 2]));
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn link_blocks(blocks: &mut [Block], resolve: &dyn Fn(&str) -> Option<String>) {
+    for block in blocks.iter_mut() {
+        match &mut block.kind {
+            BlockKind::Paragraph { content }
+            | BlockKind::Heading { content, .. }
+            | BlockKind::BulletListItem { content }
+            | BlockKind::NumberedListItem { content }
+            | BlockKind::Todo { content, .. }
+            | BlockKind::Quote { content } => link_inlines(content, resolve),
+            BlockKind::Table { rows } => {
+                for cell in rows.iter_mut().flat_map(|r| r.cells.iter_mut()) {
+                    link_inlines(cell, resolve);
+                }
+            }
+            _ => {}
+        }
+
+        // Tek başına bir bağlantıdan oluşan paragraf sayfa bağlantısı bloğu olur (Notion alt sayfaları)
+        if let BlockKind::Paragraph { content } = &block.kind {
+            if let [Inline::PageLink { page_id }] = content.as_slice() {
+                block.kind = BlockKind::PageLink {
+                    page_id: page_id.clone(),
+                };
+            }
+        }
+        link_blocks(&mut block.children, resolve);
+    }
+}
+
+fn link_inlines(inlines: &mut [Inline], resolve: &dyn Fn(&str) -> Option<String>) {
+    for inline in inlines.iter_mut() {
+        if let Inline::Link { href, content } = inline {
+            match resolve(href) {
+                Some(page_id) => *inline = Inline::PageLink { page_id },
+                None => link_inlines(content, resolve),
+            }
+        }
+    }
+}
+
+fn new_block(kind: BlockKind, children: Vec<Block>) -> Block {
+    Block {
+        id: Uuid::new_v4().to_string(),
+        kind,
+        children,
+    }
+}
+
+/// `depth` seviyesindeki bir bloğun alt bloklarının yazılacağı seviye; sınırda aynı seviyede kalır.
+fn child_depth(depth: usize) -> usize {
+    (depth + 1).min(MAX_BLOCK_DEPTH - 1)
+}
+
+/// Blok izin verilen en derin seviyedeyse alt blokları kendi yanına kardeş olarak eklenir.
+fn push_block(out: &mut Vec<Block>, mut block: Block, depth: usize) {
+    if depth + 1 >= MAX_BLOCK_DEPTH {
+        let children = std::mem::take(&mut block.children);
+        out.push(block);
+        out.extend(children);
+    } else {
+        out.push(block);
+    }
+}
+
+/// Satır içi içeriği biçim yığınlarıyla birlikte toplayan yardımcı; Markdown ve HTML
+/// dönüştürücüleri ortak kullanır.
+#[derive(Default)]
+struct InlineBuilder {
+    out: Vec<Inline>,
+    bold: u32,
+    italic: u32,
+    underline: u32,
+    strike: u32,
+    code: u32,
+    links: Vec<(String, Vec<Inline>)>,
+    /// Görselin içindeyken toplanan alternatif metin
+    image: Option<(String, String)>,
+    images: Vec<(String, String)>,
+    has_text: bool,
+}
+
+impl InlineBuilder {
+    fn styles(&self) -> Styles {
+        Styles {
+            bold: self.bold > 0,
+            italic: self.italic > 0,
+            underline: self.underline > 0,
+            strike: self.strike > 0,
+            code: self.code > 0,
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, alt)) = &mut self.image {
+            alt.push_str(text);
+            return;
+        }
+        if text.is_empty() {
+            return;
+        }
+        if !text.trim().is_empty() {
+            self.has_text = true;
+        }
+        let styles = self.styles();
+        if let Some(Inline::Text { text: last, styles: last_styles }) = self.out.last_mut() {
+            if *last_styles == styles {
+                last.push_str(text);
+                return;
+            }
+        }
+        self.out.push(Inline::Text {
+            text: text.to_string(),
+            styles,
+        });
+    }
+
+    fn ends_with_space(&self) -> bool {
+        match self.out.last() {
+            Some(Inline::Text { text, .. }) => text.ends_with(char::is_whitespace),
+            Some(_) => false,
+            None => true,
+        }
+    }
+
+    fn start_link(&mut self, href: &str) {
+        let outer = std::mem::take(&mut self.out);
+        self.links.push((href.to_string(), outer));
+    }
+
+    fn end_link(&mut self) {
+        if let Some((href, outer)) = self.links.pop() {
+            let content = std::mem::replace(&mut self.out, outer);
+            self.has_text = true;
+            self.out.push(Inline::Link { href, content });
+        }
+    }
+
+    fn start_image(&mut self, url: &str) {
+        self.image = Some((url.to_string(), String::new()));
+    }
+
+    fn end_image(&mut self) {
+        let Some((url, alt)) = self.image.take() else {
+            return;
+        };
+        // Satır içindeki görsel bağlantı olarak kalır; paragrafta tek başınaysa görsel bloğu olur
+        let label = if alt.trim().is_empty() { url.clone() } else { alt.clone() };
+        self.out.push(Inline::Link {
+            href: url.clone(),
+            content: vec![Inline::Text {
+                text: label,
+                styles: Styles::default(),
+            }],
+        });
+        if self.links.is_empty() {
+            self.images.push((url, alt));
+        } else {
+            self.has_text = true;
+        }
+    }
+
+    fn finish(mut self) -> Vec<Inline> {
+        while let Some(Inline::Text { text, .. }) = self.out.first_mut() {
+            let trimmed = text.trim_start().to_string();
+            if trimmed.is_empty() {
+                self.out.remove(0);
+            } else {
+                *text = trimmed;
+                break;
+            }
+        }
+        while let Some(Inline::Text { text, .. }) = self.out.last_mut() {
+            let trimmed = text.trim_end().to_string();
+            if trimmed.is_empty() {
+                self.out.pop();
+            } else {
+                *text = trimmed;
+                break;
+            }
+        }
+        self.out
+    }
+
+    /// Paragraf blokları; içerik sadece görsellerden oluşuyorsa her biri görsel bloğu olur.
+    fn into_blocks(self) -> Vec<Block> {
+        if !self.images.is_empty() && !self.has_text {
+            return self
+                .images
+                .into_iter()
+                .map(|(url, alt)| {
+                    let caption = Some(alt.trim().to_string()).filter(|c| !c.is_empty());
+                    new_block(
+                        BlockKind::Image {
+                            url,
+                            caption,
+                            width: None,
+                        },
+                        Vec::new(),
+                    )
+                })
+                .collect();
+        }
+        let content = self.finish();
+        if content.is_empty() {
+            return Vec::new();
+        }
+        vec![new_block(BlockKind::Paragraph { content }, Vec::new())]
+    }
+}
+
+fn plain(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text { text, .. } => out.push_str(text),
+            Inline::Link { content, .. } => out.push_str(&plain(content)),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// İlk blok bir H1 ise (ve başlık ondan alınabiliyorsa) sayfa başlığı olarak çıkarılır.
+/// Dışa aktarım içerik başlıklarını bir seviye aşağı kaydırdığı için, belgede başka H1
+/// kalmadıysa başlıklar bir seviye yukarı alınır.
+fn take_title(title: Option<String>, mut document: Document) -> (Option<String>, Document) {
+    let first = match document.first() {
+        Some(Block {
+            kind: BlockKind::Heading { level: 1, content },
+            children,
+            ..
+        }) if children.is_empty() => Some(plain(content).trim().to_string()),
+        _ => None,
+    };
+
+    let Some(first) = first.filter(|t| !t.is_empty()) else {
+        return (title, document);
+    };
+    if title.as_deref().is_some_and(|t| t != first) {
+        return (title, document);
+    }
+
+    document.remove(0);
+    if !has_h1(&document) {
+        promote_headings(&mut document);
+    }
+    (Some(first), document)
+}
+
+fn has_h1(blocks: &[Block]) -> bool {
+    blocks.iter().any(|b| {
+        matches!(b.kind, BlockKind::Heading { level: 1, .. }) || has_h1(&b.children)
+    })
+}
+
+fn promote_headings(blocks: &mut [Block]) {
+    for block in blocks {
+        if let BlockKind::Heading { level, .. } = &mut block.kind {
+            *level = (*level - 1).max(1);
+        }
+        promote_headings(&mut block.children);
+    }
+}
+
+/// Bir alıntı bloğunun ilk paragrafı alıntı metni olur, geri kalanı alt blok olarak kalır.
+fn quote_block(mut inner: Vec<Block>) -> Block {
+    let content = match inner.first() {
+        Some(Block {
+            kind: BlockKind::Paragraph { .. },
+            children,
+            ..
+        }) if children.is_empty() => match inner.remove(0).kind {
+            BlockKind::Paragraph { content } => content,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    new_block(BlockKind::Quote { content }, inner)
+}
+
+/// Markdown'ı blok belgesine çevirir. Döndürülen başlık belgenin başındaki H1'dir.
+pub fn markdown_to_document(src: &str) -> (Option<String>, Document) {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Parser::new_ext(src, options);
+    let document = md_blocks(&mut events, 0);
+    take_title(None, document)
+}
+
+/// `depth`, üretilen blokların belgedeki seviyesidir (kök 0).
+fn md_blocks(events: &mut Parser, depth: usize) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(tag) => md_block(tag, events, &mut blocks, depth),
+            Event::End(_) => break,
+            Event::Rule => blocks.push(new_block(BlockKind::Divider, Vec::new())),
+            Event::Html(html) => blocks.extend(html_document(&html, depth).1),
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Sınırdaki seviyede alıntı ve listelerin içeriğini, kapanışlarına kadar özyineleme yapmadan
+/// aynı seviyeye yazar; `>>>>…` gibi çok derin girdiler yığını taşırmaz.
+fn md_flat(events: &mut Parser, blocks: &mut Vec<Block>, depth: usize) {
+    let mut builder = InlineBuilder::default();
+    let mut open = 0;
+    while let Some(event) = events.next() {
+        let Some(event) = md_inline(&mut builder, event) else {
+            continue;
+        };
+        blocks.extend(std::mem::take(&mut builder).into_blocks());
+        match event {
+            Event::Start(Tag::BlockQuote(_) | Tag::List(_) | Tag::Item) => open += 1,
+            Event::End(_) if open == 0 => break,
+            Event::End(_) => open -= 1,
+            Event::Start(tag) => md_block(tag, events, blocks, depth),
+            Event::Rule => blocks.push(new_block(BlockKind::Divider, Vec::new())),
+            Event::Html(html) => blocks.extend(html_document(&html, depth).1),
+            _ => {}
+        }
+    }
+    blocks.extend(builder.into_blocks());
+}
+
+fn md_block(tag: Tag, events: &mut Parser, blocks: &mut Vec<Block>, depth: usize) {
+    match tag {
+        Tag::Paragraph => blocks.extend(md_inlines(events).into_blocks()),
+        Tag::Heading { level, .. } => {
+            let content = md_inlines(events).finish();
+            blocks.push(new_block(
+                BlockKind::Heading {
+                    level: level as u8,
+                    content,
+                },
+                Vec::new(),
+            ));
+        }
+        Tag::BlockQuote(_) if depth + 1 < MAX_BLOCK_DEPTH => {
+            let inner = md_blocks(events, depth + 1);
+            blocks.push(quote_block(inner));
+        }
+        Tag::BlockQuote(_) => {
+            let mut inner = Vec::new();
+            md_flat(events, &mut inner, depth);
+            push_block(blocks, quote_block(inner), depth);
+        }
+        Tag::CodeBlock(kind) => {
+            let mut code = String::new();
+            for event in events.by_ref() {
+                match event {
+                    Event::Text(text) => code.push_str(&text),
+                    Event::End(_) => break,
+                    _ => {}
+                }
+            }
+            if code.ends_with('\n') {
+                code.pop();
+            }
+            let language = match kind {
+                CodeBlockKind::Fenced(info) => info
+                    .split_whitespace()
+                    .next()
+                    .map(str::to_string),
+                CodeBlockKind::Indented => None,
+            };
+            blocks.push(new_block(BlockKind::Code { language, code }, Vec::new()));
+        }
+        Tag::HtmlBlock => {
+            let mut html = String::new();
+            for event in events.by_ref() {
+                match event {
+                    Event::Html(text) | Event::Text(text) => html.push_str(&text),
+                    Event::End(_) => break,
+                    _ => {}
+                }
+            }
+            blocks.extend(html_document(&html, depth).1);
+        }
+        Tag::List(start) => {
+            while let Some(event) = events.next() {
+                match event {
+                    Event::Start(Tag::Item) => {
+                        let item = md_item(events, start.is_some(), depth);
+                        push_block(blocks, item, depth);
+                    }
+                    Event::End(_) => break,
+                    _ => {}
+                }
+            }
+        }
+        Tag::Table(_) => blocks.push(md_table(events)),
+        _ => skip_to_end(events),
+    }
+}
+
+/// Satır içi olayı işler; satır içi değilse geri döndürür.
+fn md_inline<'a>(builder: &mut InlineBuilder, event: Event<'a>) -> Option<Event<'a>> {
+    match event {
+        Event::Text(text) => builder.text(&text),
+        Event::Code(text) | Event::InlineMath(text) => {
+            builder.code += 1;
+            builder.text(&text);
+            builder.code -= 1;
+        }
+        Event::SoftBreak => builder.text(" "),
+        Event::HardBreak => builder.text("\n"),
+        Event::InlineHtml(html) => match html.trim().to_ascii_lowercase().as_str() {
+            "<br>" | "<br/>" | "<br />" => builder.text("\n"),
+            "<u>" => builder.underline += 1,
+            "</u>" => builder.underline = builder.underline.saturating_sub(1),
+            _ => {}
+        },
+        Event::Start(Tag::Emphasis) => builder.italic += 1,
+        Event::End(TagEnd::Emphasis) => builder.italic = builder.italic.saturating_sub(1),
+        Event::Start(Tag::Strong) => builder.bold += 1,
+        Event::End(TagEnd::Strong) => builder.bold = builder.bold.saturating_sub(1),
+        Event::Start(Tag::Strikethrough) => builder.strike += 1,
+        Event::End(TagEnd::Strikethrough) => builder.strike = builder.strike.saturating_sub(1),
+        Event::Start(Tag::Link { dest_url, .. }) => builder.start_link(&dest_url),
+        Event::End(TagEnd::Link) => builder.end_link(),
+        Event::Start(Tag::Image { dest_url, .. }) => builder.start_image(&dest_url),
+        Event::End(TagEnd::Image) => builder.end_image(),
+        Event::FootnoteReference(_) => {}
+        event => return Some(event),
+    }
+    None
+}
+
+/// Paragraf, başlık ya da tablo hücresinin kapanışına kadar satır içi içeriği toplar.
+fn md_inlines(events: &mut Parser) -> InlineBuilder {
+    let mut builder = InlineBuilder::default();
+    for event in events.by_ref() {
+        if let Some(Event::End(_)) = md_inline(&mut builder, event) {
+            break;
+        }
+    }
+    builder
+}
+
+fn md_item(events: &mut Parser, numbered: bool, depth: usize) -> Block {
+    let mut builder = InlineBuilder::default();
+    let mut children = Vec::new();
+    let mut checked = None;
+
+    while let Some(event) = events.next() {
+        let Some(event) = md_inline(&mut builder, event) else {
+            continue;
+        };
+        match event {
+            Event::TaskListMarker(value) => checked = Some(value),
+            Event::End(_) => break,
+            // Gevşek listelerde maddenin metni paragraf olarak gelir
+            Event::Start(Tag::Paragraph) if builder.out.is_empty() && children.is_empty() => {
+                let paragraph = md_inlines(events);
+                builder.out = paragraph.out;
+            }
+            Event::Start(Tag::BlockQuote(_) | Tag::List(_) | Tag::Item)
+                if depth + 1 >= MAX_BLOCK_DEPTH =>
+            {
+                md_flat(events, &mut children, depth)
+            }
+            Event::Start(tag) => md_block(tag, events, &mut children, child_depth(depth)),
+            Event::Rule => children.push(new_block(BlockKind::Divider, Vec::new())),
+            _ => {}
+        }
+    }
+
+    let content = builder.finish();
+    let kind = match checked {
+        Some(checked) => BlockKind::Todo { checked, content },
+        None if numbered => BlockKind::NumberedListItem { content },
+        None => BlockKind::BulletListItem { content },
+    };
+    new_block(kind, children)
+}
+
+fn md_table(events: &mut Parser) -> Block {
+    let mut rows = Vec::new();
+    let mut cells: Vec<Vec<Inline>> = Vec::new();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(Tag::TableCell) => cells.push(md_inlines(events).finish()),
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => rows.push(TableRow {
+                cells: std::mem::take(&mut cells),
+            }),
+            Event::End(TagEnd::Table) => break,
+            _ => {}
+        }
+    }
+    new_block(BlockKind::Table { rows }, Vec::new())
+}
+
+fn skip_to_end(events: &mut Parser) {
+    let mut depth = 0;
+    for event in events.by_ref() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) if depth == 0 => break,
+            Event::End(_) => depth -= 1,
+            _ => {}
+        }
+    }
+}
+
+/// HTML'i blok belgesine çevirir. Başlık `<title>`'dan, yoksa ilk H1'den alınır.
+pub fn html_to_document(src: &str) -> (Option<String>, Document) {
+    html_document(src, 0)
+}
+
+fn html_document(src: &str, depth: usize) -> (Option<String>, Document) {
+    let html = Html::parse_document(src);
+    let title = Selector::parse("title")
+        .ok()
+        .and_then(|s| html.select(&s).next())
+        .map(|t| collapse_whitespace(&t.text().collect::<String>()).trim().to_string())
+        .filter(|t| !t.is_empty());
+
+    let body = Selector::parse("body")
+        .ok()
+        .and_then(|s| html.select(&s).next())
+        .unwrap_or_else(|| html.root_element());
+
+    let mut blocks = Vec::new();
+    html_blocks(body, &mut blocks, depth);
+    take_title(title, blocks)
+}
+
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "br", "cite", "code", "del", "em", "i", "img", "kbd", "label", "mark",
+    "s", "small", "span", "strike", "strong", "sub", "sup", "time", "u",
+];
+
+const SKIPPED_TAGS: &[&str] = &[
+    "button", "form", "head", "input", "noscript", "script", "select", "style", "template",
+    "textarea", "title",
+];
+
+/// Öğe izlenemeyecek kadar derindeyse sadece metni alınır.
+fn too_deep(element: ElementRef) -> bool {
+    element.ancestors().count() > MAX_HTML_NESTING
+}
+
+fn html_blocks(element: ElementRef, out: &mut Vec<Block>, depth: usize) {
+    let mut pending = InlineBuilder::default();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => html_text(&mut pending, text),
+            Node::Element(e) if INLINE_TAGS.contains(&e.name()) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    html_inline(&mut pending, child);
+                }
+            }
+            Node::Element(_) => {
+                out.extend(std::mem::take(&mut pending).into_blocks());
+                if let Some(child) = ElementRef::wrap(child) {
+                    html_block(child, out, depth);
+                }
+            }
+            _ => {}
+        }
+    }
+    out.extend(pending.into_blocks());
+}
+
+fn html_block(element: ElementRef, out: &mut Vec<Block>, depth: usize) {
+    if too_deep(element) {
+        let mut builder = InlineBuilder::default();
+        for text in element.text() {
+            html_text(&mut builder, text);
+        }
+        out.extend(builder.into_blocks());
+        return;
+    }
+
+    let name = element.value().name();
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse().unwrap_or(1);
+            let content = html_inlines(element).finish();
+            if !content.is_empty() {
+                out.push(new_block(BlockKind::Heading { level, content }, Vec::new()));
+            }
+        }
+        "p" => out.extend(html_inlines(element).into_blocks()),
+        "ul" | "ol" => {
+            for item in element.children().filter_map(ElementRef::wrap) {
+                if item.value().name() == "li" {
+                    push_block(out, html_item(item, name == "ol", depth), depth);
+                } else {
+                    html_block(item, out, depth);
+                }
+            }
+        }
+        "blockquote" => {
+            let mut inner = Vec::new();
+            html_blocks(element, &mut inner, child_depth(depth));
+            push_block(out, quote_block(inner), depth);
+        }
+        "pre" => {
+            let mut code: String = element.text().collect();
+            if code.ends_with('\n') {
+                code.pop();
+            }
+            let language = element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .chain(std::iter::once(element))
+                .flat_map(|e| e.value().classes())
+                .find_map(|class| class.strip_prefix("language-"))
+                .map(str::to_string);
+            out.push(new_block(BlockKind::Code { language, code }, Vec::new()));
+        }
+        "hr" => out.push(new_block(BlockKind::Divider, Vec::new())),
+        "table" => out.push(html_table(element)),
+        "figure" => {
+            let image = Selector::parse("img")
+                .ok()
+                .and_then(|s| element.select(&s).next())
+                .and_then(|img| img.value().attr("src"));
+            match image {
+                Some(url) => {
+                    let caption = Selector::parse("figcaption")
+                        .ok()
+                        .and_then(|s| element.select(&s).next())
+                        .map(|c| collapse_whitespace(&c.text().collect::<String>()).trim().to_string())
+                        .filter(|c| !c.is_empty());
+                    out.push(new_block(
+                        BlockKind::Image {
+                            url: url.to_string(),
+                            caption,
+                            width: None,
+                        },
+                        Vec::new(),
+                    ));
+                }
+                None => html_blocks(element, out, depth),
+            }
+        }
+        "iframe" | "video" | "embed" => {
+            if let Some(url) = element.value().attr("src") {
+                out.push(new_block(
+                    BlockKind::Embed {
+                        url: url.to_string(),
+                    },
+                    Vec::new(),
+                ));
+            }
+        }
+        name if SKIPPED_TAGS.contains(&name) => {}
+        // div, section, article vb. sarmalayıcılar içeriklerine göre işlenir
+        _ => html_blocks(element, out, depth),
+    }
+}
+
+fn html_item(item: ElementRef, numbered: bool, depth: usize) -> Block {
+    let mut builder = InlineBuilder::default();
+    let mut children = Vec::new();
+    let mut checked = None;
+
+    for child in item.children() {
+        match child.value() {
+            Node::Text(text) if children.is_empty() => html_text(&mut builder, text),
+            Node::Element(e) => {
+                let Some(element) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let is_checkbox = e.name() == "input" && e.attr("type") == Some("checkbox");
+                // Notion: <span class="checkbox checkbox-on">
+                let notion_checkbox = e.classes().any(|c| c == "checkbox");
+                if is_checkbox {
+                    checked = Some(e.attr("checked").is_some());
+                } else if notion_checkbox {
+                    checked = Some(e.classes().any(|c| c == "checkbox-on"));
+                } else if INLINE_TAGS.contains(&e.name()) && children.is_empty() {
+                    html_inline(&mut builder, element);
+                } else if e.name() == "p" && builder.out.is_empty() && children.is_empty() {
+                    builder.out = html_inlines(element).out;
+                } else {
+                    html_block(element, &mut children, child_depth(depth));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let content = builder.finish();
+    let kind = match checked {
+        Some(checked) => BlockKind::Todo { checked, content },
+        None if numbered => BlockKind::NumberedListItem { content },
+        None => BlockKind::BulletListItem { content },
+    };
+    new_block(kind, children)
+}
+
+fn html_table(table: ElementRef) -> Block {
+    let mut rows = Vec::new();
+    if let Ok(selector) = Selector::parse("tr") {
+        for row in table.select(&selector) {
+            let cells = row
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|c| matches!(c.value().name(), "td" | "th"))
+                .map(|c| html_inlines(c).finish())
+                .collect();
+            rows.push(TableRow { cells });
+        }
+    }
+    new_block(BlockKind::Table { rows }, Vec::new())
+}
+
+fn html_inlines(element: ElementRef) -> InlineBuilder {
+    let mut builder = InlineBuilder::default();
+    html_inline_children(&mut builder, element);
+    builder
+}
+
+fn html_inline_children(builder: &mut InlineBuilder, element: ElementRef) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => html_text(builder, text),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    html_inline(builder, child);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn html_inline(builder: &mut InlineBuilder, element: ElementRef) {
+    if too_deep(element) {
+        for text in element.text() {
+            html_text(builder, text);
+        }
+        return;
+    }
+
+    let e = element.value();
+    match e.name() {
+        "br" => builder.text("\n"),
+        "img" => {
+            if let Some(src) = e.attr("src") {
+                builder.start_image(src);
+                builder.text(e.attr("alt").unwrap_or(""));
+                builder.end_image();
+            }
+        }
+        "a" => match e.attr("href") {
+            Some(href) => {
+                builder.start_link(href);
+                html_inline_children(builder, element);
+                builder.end_link();
+            }
+            None => html_inline_children(builder, element),
+        },
+        "strong" | "b" => {
+            builder.bold += 1;
+            html_inline_children(builder, element);
+            builder.bold -= 1;
+        }
+        "em" | "i" | "cite" => {
+            builder.italic += 1;
+            html_inline_children(builder, element);
+            builder.italic -= 1;
+        }
+        "u" => {
+            builder.underline += 1;
+            html_inline_children(builder, element);
+            builder.underline -= 1;
+        }
+        "s" | "del" | "strike" => {
+            builder.strike += 1;
+            html_inline_children(builder, element);
+            builder.strike -= 1;
+        }
+        "code" | "kbd" => {
+            builder.code += 1;
+            html_inline_children(builder, element);
+            builder.code -= 1;
+        }
+        name if SKIPPED_TAGS.contains(&name) => {}
+        _ => html_inline_children(builder, element),
+    }
+}
+
+/// HTML metin düğümü: boşluklar tarayıcıdaki gibi tek boşluğa indirilir.
+fn html_text(builder: &mut InlineBuilder, text: &str) {
+    let text = collapse_whitespace(text);
+    let text = if builder.ends_with_space() {
+        text.trim_start()
+    } else {
+        &text
+    };
+    builder.text(text);
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !space {
+                out.push(' ');
+            }
+            space = true;
+        } else {
+            out.push(c);
+            space = false;
+        }
+    }
+    out
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+use sqlx::MySqlConnection;
+
+use super::blocks::{self, Block, BlockKind, Inline};
+
+/// İçerikteki sayfa bağlantıları ve bahsedilen kullanıcılar, `(block_id, hedef_id)` çiftleri olarak.
+#[derive(Debug, Default)]
+pub struct References {
+    pub page_links: BTreeSet<(String, String)>,
+    pub mentions: BTreeSet<(String, String)>,
+}
+
+pub fn extract_references(document: &[Block]) -> References {
+    let mut refs = References::default();
+    collect_blocks(document, &mut refs);
+    refs
+}
+
+fn collect_blocks(blocks: &[Block], refs: &mut References) {
+    for block in blocks {
+        match &block.kind {
+            BlockKind::PageLink { page_id } => {
+                refs.page_links.insert((block.id.clone(), page_id.clone()));
+            }
+            BlockKind::Table { rows } => {
+                for cell in rows.iter().flat_map(|row| row.cells.iter()) {
+                    collect_inline(&block.id, cell, refs);
+                }
+            }
+            kind => {
+                if let Some(content) = kind.inline_content() {
+                    collect_inline(&block.id, content, refs);
+                }
+            }
+        }
+        collect_blocks(&block.children, refs);
+    }
+}
+
+fn collect_inline(block_id: &str, items: &[Inline], refs: &mut References) {
+    for item in items {
+        match item {
+            Inline::PageLink { page_id } => {
+                refs.page_links.insert((block_id.to_string(), page_id.clone()));
+            }
+            Inline::Mention { user_id } => {
+                refs.mentions.insert((block_id.to_string(), user_id.clone()));
+            }
+            Inline::Link { content, .. } => collect_inline(block_id, content, refs),
+            Inline::Text { .. } => {}
+        }
+    }
+}
+
+/// Sayfanın bağlantı ve bahis kayıtlarını içerikle eşitler.
+/// Sadece aynı workspace'teki sayfalara bağlantılar ve workspace üyelerinden bahisler kaydedilir,
+/// diğer referanslar atlanır; blok modeline uymayan
+/// içerik referanssız kabul edilir. Bu kayıtla ilk kez bahsedilen kullanıcıları döndürür.
+pub async fn sync_references(
+    conn: &mut MySqlConnection,
+    page_id: &str,
+    content: &Value,
+    actor_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let refs = match blocks::validate(content) {
+        Ok(document) => extract_references(&document),
+        Err(_) => References::default(),
+    };
+
+    sqlx::query!("DELETE FROM page_links WHERE source_page_id = ?", page_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (block_id, target_id) in &refs.page_links {
+        sqlx::query!(
+            "INSERT INTO page_links (source_page_id, target_page_id, block_id)
+             SELECT ?, t.id, ?
+             FROM pages t
+             INNER JOIN pages s ON s.id = ? AND s.workspace_id = t.workspace_id
+             WHERE t.id = ?",
+            page_id,
+            block_id,
+            page_id,
+            target_id
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    // Bahisler, oluşturulma zamanı korunsun diye farka göre güncellenir
+    let existing: BTreeSet<(String, String)> = sqlx::query!(
+        "SELECT block_id, user_id FROM page_mentions WHERE page_id = ?",
+        page_id
+    )
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|r| (r.block_id, r.user_id))
+    .collect();
+
+    for (block_id, user_id) in existing.difference(&refs.mentions) {
+        sqlx::query!(
+            "DELETE FROM page_mentions WHERE page_id = ? AND block_id = ? AND user_id = ?",
+            page_id,
+            block_id,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let previously_mentioned: BTreeSet<&String> = existing.iter().map(|(_, u)| u).collect();
+    let mut newly_mentioned = Vec::new();
+    for (block_id, user_id) in refs.mentions.difference(&existing) {
+        let inserted = sqlx::query!(
+            "INSERT INTO page_mentions (page_id, user_id, block_id, mentioned_by)
+             SELECT ?, wm.user_id, ?, ?
+             FROM pages p
+             INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id
+             WHERE p.id = ? AND wm.user_id = ?",
+            page_id,
+            block_id,
+            actor_id,
+            page_id,
+            user_id
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        if inserted.rows_affected() > 0
+            && user_id != actor_id
+            && !previously_mentioned.contains(user_id)
+            && !newly_mentioned.contains(user_id)
+        {
+            newly_mentioned.push(user_id.clone());
+        }
+    }
+
+    Ok(newly_mentioned)
+}
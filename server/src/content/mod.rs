@@ -0,0 +1,8 @@
+pub mod blocks;
+pub mod export;
+pub mod import;
+pub mod links;
+pub mod patch;
+pub mod report;
+pub mod search;
+pub mod template;
@@ -0,0 +1,324 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Sayfa içeriği üzerinde blok seviyesinde işlemler.
+/// Bloklar `id` ile bulunur; `parent` verilmezse kök seviyesi kullanılır,
+/// `after` verilmezse blok ilgili listenin başına eklenir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BlockOperation {
+    Insert {
+        block: Value,
+        #[serde(default)]
+        parent: Option<String>,
+        #[serde(default)]
+        after: Option<String>,
+    },
+    Update {
+        id: String,
+        block: Value,
+    },
+    Move {
+        id: String,
+        #[serde(default)]
+        parent: Option<String>,
+        #[serde(default)]
+        after: Option<String>,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct BlockOperationError {
+    pub operation: usize,
+    pub message: String,
+}
+
+impl fmt::Display for BlockOperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "operation {}: {}", self.operation, self.message)
+    }
+}
+
+impl std::error::Error for BlockOperationError {}
+
+/// Boş (NULL) sayfa içeriği boş blok listesi olarak kabul edilir.
+pub fn empty_content() -> Value {
+    Value::Array(Vec::new())
+}
+
+/// İşlemleri sırayla uygular. Herhangi biri başarısız olursa içerik değişmeden kalır.
+pub fn apply_block_operations(
+    content: &mut Value,
+    operations: &[BlockOperation],
+) -> Result<(), BlockOperationError> {
+    let mut blocks = match content {
+        Value::Array(blocks) => blocks.clone(),
+        _ => {
+            return Err(BlockOperationError {
+                operation: 0,
+                message: "page content is not a block list".into(),
+            })
+        }
+    };
+
+    for (index, operation) in operations.iter().enumerate() {
+        apply_one(&mut blocks, operation).map_err(|message| BlockOperationError {
+            operation: index,
+            message,
+        })?;
+    }
+
+    *content = Value::Array(blocks);
+    Ok(())
+}
+
+fn apply_one(blocks: &mut Vec<Value>, operation: &BlockOperation) -> Result<(), String> {
+    match operation {
+        BlockOperation::Insert { block, parent, after } => {
+            if !block.is_object() {
+                return Err("block must be an object".into());
+            }
+            insert_block(blocks, block.clone(), parent.as_deref(), after.as_deref())
+        }
+        BlockOperation::Update { id, block } => {
+            let mut block = block.clone();
+            match block.as_object_mut() {
+                Some(obj) => {
+                    obj.insert("id".into(), Value::String(id.clone()));
+                }
+                None => return Err("block must be an object".into()),
+            }
+            let path = find_block(blocks, id).ok_or_else(|| format!("block {} not found", id))?;
+            let (last, parent_path) = path.split_last().unwrap();
+            container_mut(blocks, parent_path)[*last] = block;
+            Ok(())
+        }
+        BlockOperation::Move { id, parent, after } => {
+            let block = remove_block(blocks, id)?;
+            // Blok kendi alt ağacına taşınamaz: kaldırıldıktan sonra parent bulunamaz
+            insert_block(blocks, block, parent.as_deref(), after.as_deref())
+        }
+        BlockOperation::Delete { id } => remove_block(blocks, id).map(|_| ()),
+    }
+}
+
+fn insert_block(
+    blocks: &mut Vec<Value>,
+    block: Value,
+    parent: Option<&str>,
+    after: Option<&str>,
+) -> Result<(), String> {
+    let container = match parent {
+        Some(parent_id) => {
+            let path = find_block(blocks, parent_id)
+                .ok_or_else(|| format!("parent block {} not found", parent_id))?;
+            children_mut(blocks, &path)
+        }
+        None => blocks,
+    };
+
+    let position = match after {
+        Some(after_id) => {
+            container
+                .iter()
+                .position(|b| block_id(b) == Some(after_id))
+                .ok_or_else(|| format!("block {} not found in target list", after_id))?
+                + 1
+        }
+        None => 0,
+    };
+
+    container.insert(position, block);
+    Ok(())
+}
+
+fn remove_block(blocks: &mut Vec<Value>, id: &str) -> Result<Value, String> {
+    let path = find_block(blocks, id).ok_or_else(|| format!("block {} not found", id))?;
+    let (last, parent_path) = path.split_last().unwrap();
+    Ok(container_mut(blocks, parent_path).remove(*last))
+}
+
+fn block_id(block: &Value) -> Option<&str> {
+    block.get("id").and_then(Value::as_str)
+}
+
+/// Bloğun kökten itibaren indeks yolunu döndürür.
+fn find_block(blocks: &[Value], id: &str) -> Option<Vec<usize>> {
+    for (i, block) in blocks.iter().enumerate() {
+        if block_id(block) == Some(id) {
+            return Some(vec![i]);
+        }
+        if let Some(children) = block.get("children").and_then(Value::as_array) {
+            if let Some(mut path) = find_block(children, id) {
+                path.insert(0, i);
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// `path` ile gösterilen bloğun bulunduğu listeyi döndürür (boş yol = kök).
+fn container_mut<'a>(blocks: &'a mut Vec<Value>, path: &[usize]) -> &'a mut Vec<Value> {
+    match path.split_first() {
+        None => blocks,
+        Some((first, rest)) => {
+            let children = children_of(&mut blocks[*first]);
+            container_mut(children, rest)
+        }
+    }
+}
+
+/// `path` ile gösterilen bloğun çocuk listesini döndürür, yoksa oluşturur.
+fn children_mut<'a>(blocks: &'a mut Vec<Value>, path: &[usize]) -> &'a mut Vec<Value> {
+    let (last, parent_path) = path.split_last().unwrap();
+    let container = container_mut(blocks, parent_path);
+    children_of(&mut container[*last])
+}
+
+fn children_of(block: &mut Value) -> &mut Vec<Value> {
+    let obj = block.as_object_mut().expect("blocks are objects");
+    let children = obj
+        .entry("children")
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if !children.is_array() {
+        *children = Value::Array(Vec::new());
+    }
+    children.as_array_mut().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn content() -> Value {
+        json!([
+            { "id": "a", "type": "paragraph" },
+            { "id": "b", "type": "paragraph", "children": [{ "id": "c", "type": "paragraph" }] },
+            { "id": "d", "type": "paragraph" },
+        ])
+    }
+
+    /// Ağacı `a b(c) d` biçiminde yazar.
+    fn outline(blocks: &Value) -> String {
+        blocks
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|block| {
+                let id = block_id(block).unwrap().to_string();
+                match block.get("children") {
+                    Some(children) if !children.as_array().unwrap().is_empty() => {
+                        format!("{}({})", id, outline(children))
+                    }
+                    _ => id,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn apply(operations: Value) -> Result<String, BlockOperationError> {
+        let operations: Vec<BlockOperation> = serde_json::from_value(operations).unwrap();
+        let mut content = content();
+        apply_block_operations(&mut content, &operations).map(|_| outline(&content))
+    }
+
+    fn new_block(id: &str) -> Value {
+        json!({ "id": id, "type": "paragraph" })
+    }
+
+    #[test]
+    fn insert_positions() {
+        assert_eq!(apply(json!([{ "op": "insert", "block": new_block("x") }])).unwrap(), "x a b(c) d");
+        assert_eq!(
+            apply(json!([{ "op": "insert", "block": new_block("x"), "after": "d" }])).unwrap(),
+            "a b(c) d x"
+        );
+        assert_eq!(
+            apply(json!([{ "op": "insert", "block": new_block("x"), "parent": "b", "after": "c" }]))
+                .unwrap(),
+            "a b(c x) d"
+        );
+        // Çocuk listesi olmayan ebeveyn için liste oluşturulur
+        assert_eq!(
+            apply(json!([{ "op": "insert", "block": new_block("x"), "parent": "c" }])).unwrap(),
+            "a b(c(x)) d"
+        );
+    }
+
+    #[test]
+    fn insert_errors() {
+        let err = apply(json!([{ "op": "insert", "block": new_block("x"), "after": "c" }])).unwrap_err();
+        assert_eq!(err.message, "block c not found in target list");
+        let err = apply(json!([{ "op": "insert", "block": new_block("x"), "parent": "zz" }])).unwrap_err();
+        assert_eq!(err.message, "parent block zz not found");
+        let err = apply(json!([{ "op": "insert", "block": "x" }])).unwrap_err();
+        assert_eq!(err.message, "block must be an object");
+    }
+
+    #[test]
+    fn move_update_delete() {
+        assert_eq!(
+            apply(json!([{ "op": "move", "id": "a", "parent": "b", "after": "c" }])).unwrap(),
+            "b(c a) d"
+        );
+        assert_eq!(apply(json!([{ "op": "move", "id": "c", "after": "d" }])).unwrap(), "a b d c");
+        assert_eq!(apply(json!([{ "op": "delete", "id": "b" }])).unwrap(), "a d");
+
+        let operations: Vec<BlockOperation> = serde_json::from_value(json!([
+            { "op": "update", "id": "a", "block": { "id": "ignored", "type": "divider" } }
+        ]))
+        .unwrap();
+        let mut updated = content();
+        apply_block_operations(&mut updated, &operations).unwrap();
+        assert_eq!(updated[0], json!({ "id": "a", "type": "divider" }));
+    }
+
+    #[test]
+    fn move_into_own_subtree_fails() {
+        let err = apply(json!([{ "op": "move", "id": "b", "parent": "c" }])).unwrap_err();
+        assert_eq!(err.message, "parent block c not found");
+        let err = apply(json!([{ "op": "move", "id": "zz" }])).unwrap_err();
+        assert_eq!(err.message, "block zz not found");
+    }
+
+    #[test]
+    fn failed_operation_leaves_content_unchanged() {
+        let operations: Vec<BlockOperation> = serde_json::from_value(json!([
+            { "op": "delete", "id": "a" },
+            { "op": "insert", "block": new_block("x"), "parent": "b" },
+            { "op": "update", "id": "zz", "block": new_block("zz") },
+        ]))
+        .unwrap();
+        let mut content = content();
+        let err = apply_block_operations(&mut content, &operations).unwrap_err();
+        assert_eq!(err.operation, 2);
+        assert_eq!(err.to_string(), "operation 2: block zz not found");
+        assert_eq!(content, self::content());
+    }
+
+    #[test]
+    fn content_must_be_a_list() {
+        let mut content = json!({ "id": "a" });
+        let err = apply_block_operations(&mut content, &[BlockOperation::Delete { id: "a".into() }])
+            .unwrap_err();
+        assert_eq!(err.operation, 0);
+        assert_eq!(content, json!({ "id": "a" }));
+
+        let mut content = empty_content();
+        apply_block_operations(
+            &mut content,
+            &[BlockOperation::Insert { block: new_block("x"), parent: None, after: None }],
+        )
+        .unwrap();
+        assert_eq!(outline(&content), "x");
+    }
+}
@@ -0,0 +1,47 @@
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::MySqlPool;
+
+use super::blocks::{self, ValidationError};
+
+/// Blok modeline uymayan mevcut bir sayfa.
+#[derive(Debug, Serialize)]
+pub struct InvalidPage {
+    pub id: String,
+    pub workspace_id: String,
+    pub title: String,
+    pub errors: Vec<ValidationError>,
+}
+
+/// Veritabanındaki tüm sayfaları tarar ve doğrulamadan geçemeyenleri döndürür.
+/// NULL içerik boş sayfa sayılır.
+pub async fn invalid_pages(pool: &MySqlPool) -> Result<Vec<InvalidPage>, sqlx::Error> {
+    let pages = sqlx::query!("SELECT id, workspace_id, title, content FROM pages")
+        .fetch_all(pool)
+        .await?;
+
+    let mut invalid = Vec::new();
+    for page in pages {
+        let Some(raw) = page.content.as_deref() else { continue };
+
+        let errors = match serde_json::from_str::<Value>(raw) {
+            Ok(content) => match blocks::validate(&content) {
+                Ok(_) => continue,
+                Err(errors) => errors,
+            },
+            Err(e) => vec![ValidationError {
+                path: String::new(),
+                message: format!("content is not valid JSON: {}", e),
+            }],
+        };
+
+        invalid.push(InvalidPage {
+            id: page.id,
+            workspace_id: page.workspace_id,
+            title: page.title,
+            errors,
+        });
+    }
+
+    Ok(invalid)
+}
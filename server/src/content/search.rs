@@ -0,0 +1,190 @@
+use serde_json::Value;
+use sqlx::MySqlPool;
+
+/// Arama indeksine yazılan düz metin. Blok modeline uymayan eski içerikler de
+/// desteklensin diye doğrudan JSON üzerinde çalışır.
+pub fn plain_text(content: &Value) -> String {
+    let mut lines = Vec::new();
+    collect_text(content, &mut lines);
+    lines.join("\n")
+}
+
+fn collect_text(value: &Value, lines: &mut Vec<String>) {
+    match value {
+        Value::Array(items) => items.iter().for_each(|v| collect_text(v, lines)),
+        Value::Object(map) => {
+            // Bir bloğun metin parçaları tek satırda birleşir, alt bloklar ayrı satırlara gider
+            let mut line = String::new();
+            for key in ["text", "code", "caption"] {
+                if let Some(Value::String(s)) = map.get(key) {
+                    line.push_str(s);
+                }
+            }
+            if let Some(Value::Array(inline)) = map.get("content") {
+                line.push_str(&inline_text(inline));
+            }
+            if let Some(Value::Array(rows)) = map.get("rows") {
+                for row in rows {
+                    if let Some(Value::Array(cells)) = row.get("cells") {
+                        let cells: Vec<String> = cells
+                            .iter()
+                            .filter_map(Value::as_array)
+                            .map(|cell| inline_text(cell))
+                            .collect();
+                        lines.push(cells.join(" "));
+                    }
+                }
+            }
+            if !line.trim().is_empty() {
+                lines.push(line);
+            }
+            if let Some(children) = map.get("children") {
+                collect_text(children, lines);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn inline_text(items: &[Value]) -> String {
+    items
+        .iter()
+        .map(|item| match item.get("content") {
+            Some(Value::Array(nested)) => inline_text(nested),
+            _ => item.get("text").and_then(Value::as_str).unwrap_or("").to_string(),
+        })
+        .collect()
+}
+
+/// Kullanıcı sorgusunu MySQL BOOLEAN MODE ifadesine çevirir: her kelime zorunlu ve önek eşleşmeli.
+/// Arama operatörleri temizlenir; anlamlı kelime kalmazsa `None` döner.
+pub fn boolean_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = search_terms(query)
+        .into_iter()
+        .map(|term| format!("+{}*", term))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Metindeki sorgu kelimelerini `<mark>` ile işaretler. Metin HTML-escape edilir.
+pub fn highlight(text: &str, query: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let ranges = match_ranges(&chars, query);
+    mark(&chars, 0, chars.len(), &ranges)
+}
+
+/// İlk eşleşmenin çevresinden `radius` karakterlik işaretlenmiş bir kesit döndürür.
+pub fn snippet(text: &str, query: &str, radius: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let ranges = match_ranges(&chars, query);
+    let center = ranges.first().map(|r| r.0).unwrap_or(0);
+
+    let start = center.saturating_sub(radius);
+    let end = (center + radius).min(chars.len());
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.push_str(&mark(&chars, start, end, &ranges));
+    if end < chars.len() {
+        out.push('…');
+    }
+    out.replace('\n', " ")
+}
+
+/// Sorgu kelimelerinin metindeki (karakter indeksli) konumları, sıralı ve çakışmasız.
+fn match_ranges(chars: &[char], query: &str) -> Vec<(usize, usize)> {
+    let lowered: Vec<char> = chars.iter().map(|c| lower(*c)).collect();
+    let mut ranges = Vec::new();
+
+    for term in search_terms(query) {
+        let term: Vec<char> = term.chars().map(lower).collect();
+        let mut i = 0;
+        while i + term.len() <= lowered.len() {
+            if lowered[i..i + term.len()] == term[..] {
+                ranges.push((i, i + term.len()));
+                i += term.len();
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    ranges.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+fn mark(chars: &[char], start: usize, end: usize, ranges: &[(usize, usize)]) -> String {
+    let mut out = String::new();
+    let mut pos = start;
+    for &(s, e) in ranges {
+        if e <= start || s >= end {
+            continue;
+        }
+        let (s, e) = (s.max(start), e.min(end));
+        out.push_str(&escape_html(&chars[pos..s].iter().collect::<String>()));
+        out.push_str("<mark>");
+        out.push_str(&escape_html(&chars[s..e].iter().collect::<String>()));
+        out.push_str("</mark>");
+        pos = e;
+    }
+    out.push_str(&escape_html(&chars[pos..end].iter().collect::<String>()));
+    out
+}
+
+/// Tüm sayfaların `content_text` sütununu içerikten yeniden üretir.
+pub async fn reindex_all(pool: &MySqlPool) -> Result<usize, sqlx::Error> {
+    let pages = sqlx::query!("SELECT id, content FROM pages")
+        .fetch_all(pool)
+        .await?;
+
+    let mut count = 0;
+    for page in pages {
+        let text = page
+            .content
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<Value>(raw).ok())
+            .map(|content| plain_text(&content));
+
+        sqlx::query!(
+            "UPDATE pages SET content_text = ? WHERE id = ?",
+            text,
+            page.id
+        )
+        .execute(pool)
+        .await?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Şablondan sayfa oluşturulurken `{{date}}`, `{{author}}` ve `{{title}}`
+/// yer tutucularının yerine geçen değerler.
+#[derive(Debug, Clone)]
+pub struct TemplateVars {
+    pub date: String,
+    pub author: String,
+    pub title: String,
+}
+
+impl TemplateVars {
+    fn lookup(&self, name: &str) -> Option<&str> {
+        match name {
+            "date" => Some(&self.date),
+            "author" => Some(&self.author),
+            "title" => Some(&self.title),
+            _ => None,
+        }
+    }
+}
+
+/// Workspace şablonundaki bir sayfa ve alt sayfaları.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplatePage {
+    pub title: String,
+    #[serde(default)]
+    pub content: Option<Value>,
+    #[serde(default)]
+    pub children: Vec<TemplatePage>,
+}
+
+/// Metindeki yer tutucuları değiştirir; bilinmeyen değişkenler olduğu gibi bırakılır.
+pub fn render_str(input: &str, vars: &TemplateVars) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match vars.lookup(name) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&rest[start..start + 2 + end + 2]),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// İçerikteki tüm metin değerlerine `render_str` uygular.
+pub fn render_value(value: &mut Value, vars: &TemplateVars) {
+    match value {
+        Value::String(s) => *s = render_str(s, vars),
+        Value::Array(items) => items.iter_mut().for_each(|v| render_value(v, vars)),
+        Value::Object(map) => map.values_mut().for_each(|v| render_value(v, vars)),
+        _ => {}
+    }
+}
@@ -0,0 +1,104 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+
+/// Kullanıcının workspace'teki rolünü döndürür; üye değilse `None`.
+pub async fn workspace_role(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    user_id: &str,
+) -> Result<Option<String>, (StatusCode, Json<Value>)> {
+    let row = sqlx::query!(
+        "SELECT role FROM workspace_members WHERE workspace_id = ? AND user_id = ?",
+        workspace_id,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?;
+
+    Ok(row.map(|r| r.role))
+}
+
+/// Kullanıcı workspace üyesi değilse 403 döndürür.
+pub async fn require_member(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    user_id: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    match workspace_role(pool, workspace_id, user_id).await? {
+        Some(_) => Ok(()),
+        None => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        )),
+    }
+}
+
+/// Kullanıcı owner veya editor değilse 403 döndürür.
+pub async fn require_editor(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    user_id: &str,
+    viewer_message: &str,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    match workspace_role(pool, workspace_id, user_id).await? {
+        Some(role) if role == "viewer" => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": viewer_message })),
+        )),
+        Some(role) => Ok(role),
+        None => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        )),
+    }
+}
+
+/// Kullanıcı workspace'in owner'ı değilse 403 döndürür.
+pub async fn require_owner(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    user_id: &str,
+    message: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    match workspace_role(pool, workspace_id, user_id).await? {
+        Some(role) if role == "owner" => Ok(()),
+        _ => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": message })),
+        )),
+    }
+}
+
+/// Sayfanın ait olduğu workspace'i döndürür; sayfa yoksa veya çöp kutusundaysa 404.
+pub async fn page_workspace_id(
+    pool: &MySqlPool,
+    page_id: &str,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    let page = sqlx::query!(
+        "SELECT workspace_id FROM pages WHERE id = ? AND deleted_at IS NULL",
+        page_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?;
+
+    match page {
+        Some(p) => Ok(p.workspace_id),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Page not found"})),
+        )),
+    }
+}
@@ -0,0 +1,380 @@
+use axum::{
+    extract::{Multipart, Path},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::config;
+use crate::handlers::access::{page_workspace_id, require_editor, require_member};
+use crate::handlers::export_handlers::content_disposition;
+use crate::models::user::User;
+use crate::storage::{SharedStorage, StorageError};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    pub page_id: Option<String>,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub uploaded_by: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// İçerikte (ör. görsel bloğunun `url` alanında) kullanılacak indirme adresi.
+fn attachment_url(id: &str) -> String {
+    format!("/api/attachments/{}", id)
+}
+
+/// POST /api/upload-attachment (multipart)
+/// Alanlar: `page_id` ve tek bir `file`. Dönen `url` sayfa içeriğinde kullanılabilir.
+pub async fn upload_attachment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let max_bytes = config::upload_max_bytes();
+    let mut page_id = None;
+    let mut file = None;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+        )
+    })? {
+        match field.name().unwrap_or("") {
+            "page_id" => {
+                let value = field.text().await.map_err(|_| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "message": "Invalid page_id"})),
+                    )
+                })?;
+                page_id = Some(value.trim().to_string());
+            }
+            "file" => {
+                let file_name = field
+                    .file_name()
+                    .map(|n| n.rsplit(['/', '\\']).next().unwrap_or(n).trim().to_string())
+                    .filter(|n| !n.is_empty())
+                    .unwrap_or_else(|| "file".to_string());
+                let declared = field
+                    .content_type()
+                    .map(|t| t.split(';').next().unwrap_or(t).trim().to_ascii_lowercase())
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+
+                // Sınır aşılır aşılmaz okumayı bırak; dosyanın tamamı belleğe alınmaz
+                let mut data = Vec::new();
+                while let Some(chunk) = field.chunk().await.map_err(|e| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+                    )
+                })? {
+                    if data.len() + chunk.len() > max_bytes {
+                        return Err((
+                            StatusCode::PAYLOAD_TOO_LARGE,
+                            Json(json!({ "message": format!("File exceeds the {} byte limit", max_bytes) })),
+                        ));
+                    }
+                    data.extend_from_slice(&chunk);
+                }
+                file = Some((file_name, declared, data));
+            }
+            _ => {}
+        }
+    }
+
+    let page_id = page_id.filter(|p| !p.is_empty()).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "page_id is required"})),
+        )
+    })?;
+    let (file_name, content_type, data) = file.ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "file is required"})),
+        )
+    })?;
+
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    require_editor(&pool, &workspace_id, &user.id, "Viewers cannot upload files").await?;
+
+    if data.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "File is empty"})),
+        ));
+    }
+    if !config::upload_allowed_types().contains(&content_type) {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({ "message": format!("File type {} is not allowed", content_type) })),
+        ));
+    }
+    // Görseller satır içi gösterildiği için bildirilen tip içerikle doğrulanır
+    if content_type.starts_with("image/") && sniff_image(&data) != Some(content_type.as_str()) {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({ "message": "File content does not match its type"})),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let storage_key = format!("{}/{}", workspace_id, id);
+    let size_bytes = data.len() as u64;
+
+    storage
+        .put(&storage_key, data.into(), &content_type)
+        .await
+        .map_err(|e| {
+            tracing::error!(attachment_id = %id, error = %e, "failed to store attachment");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Failed to store file"})),
+            )
+        })?;
+
+    let result = sqlx::query!(
+        "INSERT INTO attachments (id, workspace_id, page_id, storage_key, file_name, content_type, size_bytes, uploaded_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        id,
+        workspace_id,
+        page_id,
+        storage_key,
+        file_name,
+        content_type,
+        size_bytes,
+        user.id
+    )
+    .execute(&pool)
+    .await;
+
+    if result.is_err() {
+        // Kaydı olmayan dosya depolamada kalmasın
+        let _ = storage.delete(&storage_key).await;
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        ));
+    }
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "attachment.uploaded",
+            target_type: "attachment",
+            target_id: Some(&id),
+            before: None,
+            after: Some(json!({ "page_id": page_id, "file_name": file_name, "content_type": content_type, "size_bytes": size_bytes })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "File uploaded successfully",
+        "attachment_id": id,
+        "url": attachment_url(&id),
+        "file_name": file_name,
+        "content_type": content_type,
+        "size_bytes": size_bytes
+         })),
+    ))
+}
+
+/// GET /api/attachments/:id — sadece workspace üyeleri indirebilir.
+pub async fn download_attachment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    Path(attachment_id): Path<String>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let attachment = sqlx::query!(
+        "SELECT workspace_id, storage_key, file_name, content_type FROM attachments WHERE id = ? AND page_id IS NOT NULL",
+        attachment_id
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Attachment not found"})),
+        )
+    })?;
+
+    require_member(&pool, &attachment.workspace_id, &user.id).await?;
+
+    let data = storage.get(&attachment.storage_key).await.map_err(|e| match e {
+        StorageError::NotFound => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Attachment not found"})),
+        ),
+        e => {
+            tracing::error!(%attachment_id, error = %e, "failed to read attachment");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Failed to read file"})),
+            )
+        }
+    })?;
+
+    // Görseller sayfada gösterilir, diğer her şey indirilir
+    let disposition = if attachment.content_type.starts_with("image/") {
+        "inline"
+    } else {
+        "attachment"
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (header::CONTENT_DISPOSITION, content_disposition(disposition, &attachment.file_name)),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+/// GET /api/pages/:id/attachments
+pub async fn get_page_attachments(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(page_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    require_member(&pool, &workspace_id, &user.id).await?;
+
+    let attachments = sqlx::query_as!(
+        Attachment,
+        "SELECT id, page_id, file_name, content_type, size_bytes, uploaded_by, created_at
+         FROM attachments WHERE page_id = ? ORDER BY created_at",
+        page_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let attachments: Vec<Value> = attachments
+        .into_iter()
+        .map(|a| {
+            let url = attachment_url(&a.id);
+            let mut value = json!(a);
+            value["url"] = json!(url);
+            value
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(attachments)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteAttachmentPayload {
+    pub id: String,
+}
+
+pub async fn delete_attachment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<DeleteAttachmentPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let attachment = sqlx::query!(
+        "SELECT workspace_id, page_id, storage_key, file_name FROM attachments WHERE id = ? AND page_id IS NOT NULL",
+        payload.id
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Attachment not found"})),
+        )
+    })?;
+
+    require_editor(&pool, &attachment.workspace_id, &user.id, "Viewers cannot delete files").await?;
+
+    storage.delete(&attachment.storage_key).await.map_err(|e| {
+        tracing::error!(attachment_id = %payload.id, error = %e, "failed to delete attachment");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Failed to delete file"})),
+        )
+    })?;
+
+    sqlx::query!("DELETE FROM attachments WHERE id = ?", payload.id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error"})),
+            )
+        })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&attachment.workspace_id),
+            actor_id: Some(&user.id),
+            action: "attachment.deleted",
+            target_type: "attachment",
+            target_id: Some(&payload.id),
+            before: Some(json!({ "page_id": attachment.page_id, "file_name": attachment.file_name })),
+            after: None,
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Attachment deleted successfully" }))))
+}
+
+/// Dosyanın ilk baytlarından görsel tipini bulur; izin verilen görsel tipleri dışında `None`.
+fn sniff_image(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
@@ -0,0 +1,201 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+
+use crate::handlers::access::workspace_role;
+use crate::models::user::User;
+
+/// İstenebilecek en büyük sayfa numarası; ofset hesabının taşmaması için sınırlanır.
+const MAX_PAGE: u32 = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: String,
+    pub workspace_id: Option<String>,
+    pub actor_id: Option<String>,
+    pub actor_username: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<String>,
+    pub before: Option<sqlx::types::Json<Value>>,
+    pub after: Option<sqlx::types::Json<Value>>,
+    pub ip: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditParams {
+    /// Sadece admin görünümünde kullanılır
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub actor_id: Option<String>,
+    #[serde(default)]
+    pub action: Option<String>,
+    #[serde(default)]
+    pub target_id: Option<String>,
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+/// GET /api/workspaces/:id/activity?action=page.deleted&actor_id=...&since=...&page=1
+/// Bütün üyeler görebilir; IP adresleri sadece owner'a gösterilir.
+pub async fn get_workspace_activity(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(workspace_id): Path<String>,
+    Query(params): Query<AuditParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let role = workspace_role(&pool, &workspace_id, &user.id)
+        .await?
+        .ok_or_else(|| {
+            (
+                StatusCode::FORBIDDEN,
+                Json(json!({ "message": "You don't have access to this workspace"})),
+            )
+        })?;
+
+    let (mut events, total) = fetch_events(&pool, Some(&workspace_id), &params).await?;
+    if role != "owner" {
+        for event in &mut events {
+            event.ip = None;
+        }
+    }
+
+    Ok((StatusCode::OK, Json(page_body(events, total, &params))))
+}
+
+/// GET /api/admin/audit — bütün workspace'ler ve giriş olayları, sadece admin
+pub async fn get_audit_log(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Query(params): Query<AuditParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if user.role != "admin" {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "Only admins can view the audit log"})),
+        ));
+    }
+
+    let (events, total) = fetch_events(&pool, params.workspace_id.as_deref(), &params).await?;
+
+    Ok((StatusCode::OK, Json(page_body(events, total, &params))))
+}
+
+fn pagination(params: &AuditParams) -> (u32, u32) {
+    let page = params.page.unwrap_or(1).clamp(1, MAX_PAGE);
+    let per_page = params.per_page.unwrap_or(50).clamp(1, 200);
+    (page, per_page)
+}
+
+fn page_body(events: Vec<AuditEntry>, total: i64, params: &AuditParams) -> Value {
+    let (page, per_page) = pagination(params);
+    json!({
+        "events": events,
+        "total": total,
+        "page": page,
+        "per_page": per_page,
+    })
+}
+
+async fn fetch_events(
+    pool: &MySqlPool,
+    workspace_id: Option<&str>,
+    params: &AuditParams,
+) -> Result<(Vec<AuditEntry>, i64), (StatusCode, Json<Value>)> {
+    let (page, per_page) = pagination(params);
+    let offset = (page - 1) * per_page;
+
+    let total = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "total!: i64"
+        FROM audit_events e
+        WHERE (? IS NULL OR e.workspace_id = ?)
+          AND (? IS NULL OR e.actor_id = ?)
+          AND (? IS NULL OR e.action = ?)
+          AND (? IS NULL OR e.target_id = ?)
+          AND (? IS NULL OR e.created_at >= ?)
+          AND (? IS NULL OR e.created_at < ?)
+        "#,
+        workspace_id,
+        workspace_id,
+        params.actor_id,
+        params.actor_id,
+        params.action,
+        params.action,
+        params.target_id,
+        params.target_id,
+        params.since,
+        params.since,
+        params.until,
+        params.until
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?
+    .total;
+
+    let events = sqlx::query_as!(
+        AuditEntry,
+        r#"
+        SELECT e.id, e.workspace_id, e.actor_id, u.username as "actor_username?",
+               e.action, e.target_type, e.target_id,
+               e.before_summary as "before: sqlx::types::Json<Value>",
+               e.after_summary as "after: sqlx::types::Json<Value>",
+               e.ip, e.created_at
+        FROM audit_events e
+        LEFT JOIN users u ON u.id = e.actor_id
+        WHERE (? IS NULL OR e.workspace_id = ?)
+          AND (? IS NULL OR e.actor_id = ?)
+          AND (? IS NULL OR e.action = ?)
+          AND (? IS NULL OR e.target_id = ?)
+          AND (? IS NULL OR e.created_at >= ?)
+          AND (? IS NULL OR e.created_at < ?)
+        ORDER BY e.created_at DESC, e.id
+        LIMIT ? OFFSET ?
+        "#,
+        workspace_id,
+        workspace_id,
+        params.actor_id,
+        params.actor_id,
+        params.action,
+        params.action,
+        params.target_id,
+        params.target_id,
+        params.since,
+        params.since,
+        params.until,
+        params.until,
+        per_page,
+        offset
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((events, total))
+}
@@ -6,10 +6,12 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::MySqlPool;
+use tower_cookies::Cookies;
 use uuid::Uuid;
+use crate::audit::{record, AuditEvent, ClientIp};
 use crate::models::user::User;
 
-use super::jwt::generate_token;
+use super::jwt::{generate_token, verify_token};
 
 #[derive(Deserialize)]
 pub struct SignupPayload {
@@ -26,6 +28,7 @@ pub struct LoginPayload {
 
 pub async fn signup(
     State(pool): State<MySqlPool>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<SignupPayload>,
 ) -> Result<StatusCode, (StatusCode, Json<serde_json::Value>)> {
     let email_existing = sqlx::query!(
@@ -59,7 +62,8 @@ pub async fn signup(
 
     let uuid = Uuid::new_v4().to_string();
 
-    let result = sqlx::query!(
+    // Kayıt başarısızsa olay yazılmaz; audit log'da var olmayan bir kullanıcı görünmesin
+    sqlx::query!(
         "INSERT INTO users (id, username, email, password) VALUES (?, ?, ?, ?)",
         uuid,
         payload.username,
@@ -73,28 +77,48 @@ pub async fn signup(
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "message": "Database error" })),
         )
-    });
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: None,
+            actor_id: Some(&uuid),
+            action: "user.signed_up",
+            target_type: "user",
+            target_id: Some(&uuid),
+            before: None,
+            after: Some(json!({ "username": payload.username, "email": payload.email })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
 
     Ok(StatusCode::CREATED)
 }
 
 pub async fn login(
     State(pool): State<MySqlPool>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<LoginPayload>,
 ) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
 
-    let user = sqlx::query!(
+    let user = match sqlx::query!(
         "SELECT id, email, password FROM users WHERE email = ?",
         payload.email
     )
     .fetch_one(&pool)
     .await
-    .map_err(|_| {
-        (
-            StatusCode::UNAUTHORIZED,
-            Json(json!({ "message": "Invalid email" })),
-        )
-    })?;
+    {
+        Ok(user) => user,
+        Err(_) => {
+            record_login_failure(&pool, None, &payload.email, ip.as_deref()).await;
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "message": "Invalid email" })),
+            ));
+        }
+    };
 
     let is_valid_password = bcrypt::verify(payload.password, &user.password).map_err(|_| {
         (
@@ -104,12 +128,28 @@ pub async fn login(
     })?;
 
     if !is_valid_password {
+        record_login_failure(&pool, Some(&user.id), &payload.email, ip.as_deref()).await;
         return Err((
             StatusCode::UNAUTHORIZED,
             Json(json!({ "message": "Invalid password" })),
         ));
     }
 
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: None,
+            actor_id: Some(&user.id),
+            action: "user.logged_in",
+            target_type: "user",
+            target_id: Some(&user.id),
+            before: None,
+            after: None,
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+
     // jwt token adjustment
 
     let token = generate_token(&user.id);
@@ -147,7 +187,33 @@ pub async fn me(Extension(user): Extension<User>) -> Json<Profile> {
     })
 }
 
-pub async fn logout() -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+pub async fn logout(
+    State(pool): State<MySqlPool>,
+    ClientIp(ip): ClientIp,
+    cookies: Cookies,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+
+    // Çıkış her durumda yapılır; token geçerliyse kimin çıktığı kaydedilir
+    let user_id = cookies
+        .get("axtoken")
+        .and_then(|c| verify_token(c.value()).ok())
+        .map(|data| data.claims.sub);
+    if let Some(user_id) = &user_id {
+        record(
+            &pool,
+            AuditEvent {
+                workspace_id: None,
+                actor_id: Some(user_id),
+                action: "user.logged_out",
+                target_type: "user",
+                target_id: Some(user_id),
+                before: None,
+                after: None,
+                ip: ip.as_deref(),
+            },
+        )
+        .await;
+    }
 
     let mut headers = HeaderMap::new();
 
@@ -164,3 +230,21 @@ pub async fn logout() -> Result<Response, (StatusCode, Json<serde_json::Value>)>
 
     Ok((headers, body).into_response())
 }
+
+/// Başarısız girişler de kaydedilir; parola hiçbir zaman loglanmaz.
+async fn record_login_failure(pool: &MySqlPool, user_id: Option<&str>, email: &str, ip: Option<&str>) {
+    record(
+        pool,
+        AuditEvent {
+            workspace_id: None,
+            actor_id: None,
+            action: "user.login_failed",
+            target_type: "user",
+            target_id: user_id,
+            before: None,
+            after: Some(json!({ "email": email })),
+            ip,
+        },
+    )
+    .await;
+}
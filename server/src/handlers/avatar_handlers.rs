@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Multipart, Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::avatar::{
+    avatar_url, is_version, nearest_size, parse_avatar_url, process_avatar, storage_key,
+    AVATAR_SIZES, DEFAULT_AVATAR_SIZE,
+};
+use crate::config;
+use crate::models::user::User;
+use crate::storage::{SharedStorage, StorageError};
+
+/// POST /api/upload-avatar (multipart, tek `file` alanı)
+/// Görsel kare kırpılıp birkaç boyutta saklanır ve `users.avatar` yeni adrese güncellenir.
+pub async fn upload_avatar(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let max_bytes = config::avatar_max_bytes();
+    let mut data = None;
+
+    while let Some(mut field) = multipart.next_field().await.map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+        )
+    })? {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let mut buf = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+            )
+        })? {
+            if buf.len() + chunk.len() > max_bytes {
+                return Err((
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    Json(json!({ "message": format!("Avatar exceeds the {} byte limit", max_bytes) })),
+                ));
+            }
+            buf.extend_from_slice(&chunk);
+        }
+        data = Some(buf);
+    }
+
+    let data = data.filter(|d| !d.is_empty()).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "file is required"})),
+        )
+    })?;
+
+    // Çözme ve yeniden boyutlandırma CPU'ya bağlı olduğu için engelleyen iş parçacığında yapılır
+    let images = tokio::task::spawn_blocking(move || process_avatar(&data))
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Failed to process image"})),
+            )
+        })?
+        .map_err(|e| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({ "message": e.to_string() })),
+            )
+        })?;
+
+    let version = Uuid::new_v4().simple().to_string();
+    for (size, png) in images {
+        let key = storage_key(&user.id, &version, size);
+        if let Err(e) = storage.put(&key, png.into(), "image/png").await {
+            tracing::error!(%key, error = %e, "failed to store avatar");
+            delete_version(&storage, &user.id, &version).await;
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Failed to store avatar"})),
+            ));
+        }
+    }
+
+    let url = avatar_url(&user.id, &version);
+    let updated = sqlx::query!("UPDATE users SET avatar = ? WHERE id = ?", url, user.id)
+        .execute(&pool)
+        .await;
+    if updated.is_err() {
+        delete_version(&storage, &user.id, &version).await;
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        ));
+    }
+
+    // Önceki yüklenmiş avatar artık kullanılmıyor
+    if let Some(old) = parse_avatar_url(&user.avatar, &user.id) {
+        delete_version(&storage, &user.id, old).await;
+    }
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: None,
+            actor_id: Some(&user.id),
+            action: "user.avatar_changed",
+            target_type: "user",
+            target_id: Some(&user.id),
+            before: Some(json!({ "avatar": user.avatar })),
+            after: Some(json!({ "avatar": url })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+
+    let sizes: BTreeMap<u32, String> = AVATAR_SIZES
+        .iter()
+        .map(|&size| (size, format!("{}?size={}", url, size)))
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Avatar updated successfully",
+        "avatar": url,
+        "sizes": sizes
+         })),
+    ))
+}
+
+async fn delete_version(storage: &SharedStorage, user_id: &str, version: &str) {
+    for size in AVATAR_SIZES {
+        let key = storage_key(user_id, version, size);
+        if let Err(e) = storage.delete(&key).await {
+            tracing::warn!(%key, error = %e, "failed to delete avatar");
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvatarParams {
+    #[serde(default)]
+    pub size: Option<u32>,
+}
+
+/// GET /api/avatars/:userId/:version?size=64
+/// Oturum gerektirmez (`<img>` etiketlerinden yüklenir); her yüklemede sürüm değiştiği için
+/// yanıt süresiz önbelleğe alınabilir.
+pub async fn get_avatar(
+    Extension(storage): Extension<SharedStorage>,
+    Path((user_id, version)): Path<(String, String)>,
+    Query(params): Query<AvatarParams>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let valid_user = !user_id.is_empty()
+        && user_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !valid_user || !is_version(&version) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Avatar not found"})),
+        ));
+    }
+
+    let size = nearest_size(params.size.unwrap_or(DEFAULT_AVATAR_SIZE));
+    let data = storage
+        .get(&storage_key(&user_id, &version, size))
+        .await
+        .map_err(|e| match e {
+            StorageError::NotFound => (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "message": "Avatar not found"})),
+            ),
+            e => {
+                tracing::error!(%user_id, %version, error = %e, "failed to read avatar");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Failed to read avatar"})),
+                )
+            }
+        })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "public, max-age=31536000, immutable"),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+        ],
+        data,
+    )
+        .into_response())
+}
@@ -1,65 +1,133 @@
 use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::MySqlPool;
 use uuid::Uuid;
-use sqlx::types::*;
 
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::content::blocks;
+use crate::content::links::sync_references;
+use crate::content::patch::{apply_block_operations, empty_content, BlockOperation};
+use crate::content::search::plain_text;
+use crate::handlers::access::{page_workspace_id, require_editor};
+use crate::handlers::page_tree::{check_parent, subtree_ids};
+use crate::handlers::template_handlers::instantiate_workspace_template;
 use crate::models::user::User;
+use crate::notifications::{notify, NewNotification, NotificationKind};
+use crate::webhooks::{dispatch, WebhookEvent};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateWorkspacePayload {
     pub name: String,
     pub description: String,
     pub emails: Vec<String>,
+    /// Verilirse workspace bu şablondaki sayfa ağacıyla oluşturulur
+    #[serde(default)]
+    pub template_id: Option<String>,
 }
 
 pub async fn create_workspace(
     Extension(pool): Extension<MySqlPool>,
     Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<CreateWorkspacePayload>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    // Şablon, hiçbir kayıt eklenmeden önce doğrulanır
+    if let Some(template_id) = &payload.template_id {
+        let template = sqlx::query!("SELECT id FROM workspace_templates WHERE id = ?", template_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+        if template.is_none() {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({ "message": "Workspace template not found"})),
+            ));
+        }
+    }
+
     let ws_id = Uuid::new_v4().to_string();
 
-    let result = sqlx::query!(
+    // Workspace, owner üyeliği ve şablon sayfaları birlikte oluşur ya da hiçbiri oluşmaz
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    sqlx::query!(
         "INSERT INTO workspaces (id,name, description, owner_id) VALUES (?, ?, ?, ?)",
         ws_id,
         payload.name,
         payload.description,
         user.id
     )
-    .execute(&pool)
+    .execute(&mut *tx)
     .await
     .map_err(|_| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "message": "Database error" })),
         )
-    });
+    })?;
 
     // Then add the creator as an owner in workspace_members
-    let member_result = sqlx::query!(
+    sqlx::query!(
         "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES (?, ?, 'owner')",
         ws_id,
         user.id
     )
-    .execute(&pool)
+    .execute(&mut *tx)
     .await
     .map_err(|_| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "message": "Failed to add workspace member" })),
         )
-    });
+    })?;
 
-    //TODO: send email to all emails
+    if let Some(template_id) = &payload.template_id {
+        instantiate_workspace_template(&mut tx, template_id, &ws_id, &user).await?;
+    }
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    // Davet edilenler onay vermeden üye yapılmaz; üyeler /api/add-member ile eklenir
+    //TODO: send invitation email to all emails
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&ws_id),
+            actor_id: Some(&user.id),
+            action: "workspace.created",
+            target_type: "workspace",
+            target_id: Some(&ws_id),
+            before: None,
+            after: Some(json!({ "name": payload.name, "template_id": payload.template_id })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
 
     let body = Json(json!({
         "message": "workspace created successfully",
         "workspaceId": ws_id
     }));
 
-    (StatusCode::OK, body)
+    Ok((StatusCode::OK, body))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -149,6 +217,7 @@ pub struct Page {
     pub id: String,
     pub title: String,
     pub workspace_id: String,
+    pub parent_id: Option<String>,
 }
 
 pub async fn get_workspace_pages(
@@ -180,7 +249,7 @@ pub async fn get_workspace_pages(
 
     let pages = sqlx::query_as!(
         Page,
-        "SELECT id, title, workspace_id FROM pages WHERE workspace_id = ?",
+        "SELECT id, title, workspace_id, parent_id FROM pages WHERE workspace_id = ? AND deleted_at IS NULL",
         workspace_id
     )
     .fetch_all(&pool)
@@ -234,11 +303,14 @@ pub async fn get_workspace_members(
 pub struct CreatePagePayload {
     pub title: String,
     pub workspace_id: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
 }
 
 pub async fn create_page(
     Extension(pool): Extension<MySqlPool>,
     Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<CreatePagePayload>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     // Önce kullanıcının workspace'teki rolünü kontrol et
@@ -273,12 +345,17 @@ pub async fn create_page(
         _ => {} // owner veya editor ise devam et
     }
 
+    if let Some(parent_id) = &payload.parent_id {
+        check_parent(&pool, parent_id, &payload.workspace_id).await?;
+    }
+
     let page_id = Uuid::new_v4().to_string();
     let result = sqlx::query!(
-        "INSERT INTO pages (id, title, workspace_id, created_by) VALUES (?, ?, ?, ?)",
+        "INSERT INTO pages (id, title, workspace_id, parent_id, created_by) VALUES (?, ?, ?, ?, ?)",
         page_id,
         payload.title,
         payload.workspace_id,
+        payload.parent_id,
         user.id
     )
     .execute(&pool)
@@ -290,6 +367,28 @@ pub async fn create_page(
         )
     })?;
 
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&payload.workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.created",
+            target_type: "page",
+            target_id: Some(&page_id),
+            before: None,
+            after: Some(json!({ "title": payload.title, "parent_id": payload.parent_id })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &payload.workspace_id,
+        WebhookEvent::PageCreated,
+        json!({ "page_id": page_id, "title": payload.title, "parent_id": payload.parent_id, "actor_id": user.id }),
+    )
+    .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({ "message": "Page created successfully",
@@ -303,6 +402,7 @@ pub struct PageResponse {
     pub id: String,
     pub title: String,
     pub workspace_id: String,
+    pub parent_id: Option<String>,
     pub created_by: String,
     pub content: Option<Value>,
 }
@@ -315,7 +415,7 @@ pub async fn get_page(
 
     let page = sqlx::query_as!(
         PageResponse,
-        "SELECT id, title, workspace_id, created_by,content FROM pages WHERE id = ? ",
+        "SELECT id, title, workspace_id, parent_id, created_by,content FROM pages WHERE id = ? AND deleted_at IS NULL",
         page_id,
     )
     .fetch_optional(&pool)
@@ -339,11 +439,12 @@ pub struct RenamePagePayload {
 pub async fn rename_page(
     Extension(pool): Extension<MySqlPool>,
     Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<RenamePagePayload>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     // Önce sayfanın workspace_id'sini al
     let page = sqlx::query!(
-        "SELECT workspace_id FROM pages WHERE id = ?",
+        "SELECT workspace_id, title, created_by FROM pages WHERE id = ? AND deleted_at IS NULL",
         payload.id
     )
     .fetch_optional(&pool)
@@ -355,13 +456,14 @@ pub async fn rename_page(
         )
     })?;
 
-    let workspace_id = match page {
-        Some(p) => p.workspace_id,
+    let page = match page {
+        Some(p) => p,
         None => return Err((
             StatusCode::NOT_FOUND,
             Json(json!({ "message": "Page not found"})),
         )),
     };
+    let workspace_id = page.workspace_id.clone();
 
     // Kullanıcının workspace'teki rolünü kontrol et
     let user_role = sqlx::query!(
@@ -396,7 +498,7 @@ pub async fn rename_page(
     }
 
     let result = sqlx::query!(
-        "UPDATE pages SET title = ? WHERE id = ?",
+        "UPDATE pages SET title = ? WHERE id = ? AND deleted_at IS NULL",
         payload.title,
         payload.id
     )
@@ -408,6 +510,44 @@ pub async fn rename_page(
             Json(json!({ "message": "Database error" })),
         )
     })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.renamed",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: Some(json!({ "title": page.title })),
+            after: Some(json!({ "title": payload.title })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &workspace_id,
+        WebhookEvent::PageRenamed,
+        json!({ "page_id": payload.id, "old_title": page.title, "new_title": payload.title, "actor_id": user.id }),
+    )
+    .await;
+
+    // Sayfayı başkası yeniden adlandırdıysa oluşturana haber ver
+    if page.created_by != user.id && page.title != payload.title {
+        notify(
+            &pool,
+            NewNotification {
+                user_id: &page.created_by,
+                kind: NotificationKind::PageRenamed,
+                actor_id: Some(&user.id),
+                workspace_id: Some(&workspace_id),
+                page_id: Some(&payload.id),
+                data: json!({ "old_title": page.title, "new_title": payload.title }),
+            },
+        )
+        .await;
+    }
     Ok((StatusCode::OK, Json(json!({ "message": "Page renamed successfully" }))))
 }
 
@@ -419,11 +559,12 @@ pub struct DeletePagePayload {
 pub async fn delete_page(
     Extension(pool): Extension<MySqlPool>,
     Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<DeletePagePayload>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     // Önce sayfanın workspace_id'sini al
     let page = sqlx::query!(
-        "SELECT workspace_id FROM pages WHERE id = ?",
+        "SELECT workspace_id, title, created_by FROM pages WHERE id = ? AND deleted_at IS NULL",
         payload.id
     )
     .fetch_optional(&pool)
@@ -435,13 +576,14 @@ pub async fn delete_page(
         )
     })?;
 
-    let workspace_id = match page {
-        Some(p) => p.workspace_id,
+    let page = match page {
+        Some(p) => p,
         None => return Err((
             StatusCode::NOT_FOUND,
             Json(json!({ "message": "Page not found"})),
         )),
     };
+    let workspace_id = page.workspace_id.clone();
 
     // Kullanıcının workspace'teki rolünü kontrol et
     let user_role = sqlx::query!(
@@ -475,19 +617,84 @@ pub async fn delete_page(
         _ => {} // owner veya editor ise devam et
     }
 
-    let result = sqlx::query!(
-        "DELETE FROM pages WHERE id = ?",
-        payload.id
-    )
-    .execute(&pool)
-    .await
-    .map_err(|_| {
+    // Kalıcı silme yerine alt sayfalarla birlikte çöp kutusuna taşı.
+    // Aynı zaman damgası, geri yüklemede birlikte silinen sayfaları bulmak için kullanılır.
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let page_ids = subtree_ids(&mut tx, &payload.id, false).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let deleted_at = Utc::now();
+    for page_id in &page_ids {
+        sqlx::query!(
+            "UPDATE pages SET deleted_at = ?, deleted_by = ? WHERE id = ? AND deleted_at IS NULL",
+            deleted_at,
+            user.id,
+            page_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+    }
+
+    tx.commit().await.map_err(|_| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "message": "Database error" })),
         )
     })?;
-    Ok((StatusCode::OK, Json(json!({ "message": "Page deleted successfully" }))))
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.deleted",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: Some(json!({ "title": page.title })),
+            after: Some(json!({ "trashed_page_ids": page_ids })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &workspace_id,
+        WebhookEvent::PageDeleted,
+        json!({ "page_id": payload.id, "title": page.title, "deleted_page_ids": page_ids, "actor_id": user.id }),
+    )
+    .await;
+
+    if page.created_by != user.id {
+        notify(
+            &pool,
+            NewNotification {
+                user_id: &page.created_by,
+                kind: NotificationKind::PageDeleted,
+                actor_id: Some(&user.id),
+                workspace_id: Some(&workspace_id),
+                page_id: Some(&payload.id),
+                data: json!({ "title": page.title }),
+            },
+        )
+        .await;
+    }
+    Ok((StatusCode::OK, Json(json!({ "message": "Page moved to trash" }))))
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -499,16 +706,46 @@ pub struct UpdatePagePayload {
 pub async fn update_page(
     Extension(pool): Extension<MySqlPool>,
     Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
     Json(payload): Json<UpdatePagePayload>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    
+    let workspace_id = page_workspace_id(&pool, &payload.id).await?;
+    require_editor(&pool, &workspace_id, &user.id, "Viewers cannot edit pages").await?;
+
+    blocks::validate(&payload.content).map_err(|errors| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "message": "Invalid page content", "errors": errors })),
+        )
+    })?;
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let previous = sqlx::query!(
+        r#"SELECT content as "content: sqlx::types::Json<Value>" FROM pages WHERE id = ? FOR UPDATE"#,
+        payload.id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
 
     let result = sqlx::query!(
-        "UPDATE pages SET content = ? WHERE id = ?",
-        sqlx::types::Json(payload.content),
+        "UPDATE pages SET content = ?, content_text = ? WHERE id = ? AND deleted_at IS NULL",
+        sqlx::types::Json(&payload.content),
+        plain_text(&payload.content),
         payload.id
     )
-    .execute(&pool)
+    .execute(&mut *tx)
     .await
     .map_err(|_| {
         (
@@ -516,5 +753,212 @@ pub async fn update_page(
             Json(json!({ "message": "Database error" })),
         )
     })?;
+
+    let mentioned = sync_references(&mut tx, &payload.id, &payload.content, &user.id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    notify_mentions(&pool, &mentioned, &workspace_id, &payload.id, &user.id).await;
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.updated",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: previous.content.map(|c| content_summary(&c.0)),
+            after: Some(content_summary(&payload.content)),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &workspace_id,
+        WebhookEvent::PageUpdated,
+        json!({ "page_id": payload.id, "actor_id": user.id }),
+    )
+    .await;
+
     Ok((StatusCode::OK, Json(json!({ "message": "Page updated successfully" }))))
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PatchPagePayload {
+    pub id: String,
+    /// RFC 6902 JSON Patch, blok işlemlerinden önce uygulanır
+    #[serde(default)]
+    pub patch: Option<json_patch::Patch>,
+    #[serde(default)]
+    pub operations: Vec<BlockOperation>,
+}
+
+pub async fn patch_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<PatchPagePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &payload.id).await?;
+    require_editor(&pool, &workspace_id, &user.id, "Viewers cannot edit pages").await?;
+
+    // Eşzamanlı kayıtlar birbirini ezmesin diye satırı kilitle
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let page = sqlx::query!(
+        r#"SELECT content as "content: sqlx::types::Json<Value>" FROM pages WHERE id = ? AND deleted_at IS NULL FOR UPDATE"#,
+        payload.id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let mut content = page.content.map(|c| c.0).unwrap_or_else(empty_content);
+    let before = content_summary(&content);
+
+    if let Some(patch) = &payload.patch {
+        json_patch::patch(&mut content, patch).map_err(|e| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({
+                    "message": "Patch could not be applied",
+                    "operation": e.operation,
+                    "path": e.path.to_string(),
+                    "error": e.kind.to_string(),
+                })),
+            )
+        })?;
+    }
+
+    apply_block_operations(&mut content, &payload.operations).map_err(|e| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "message": "Block operation could not be applied",
+                "operation": e.operation,
+                "error": e.message,
+            })),
+        )
+    })?;
+
+    blocks::validate(&content).map_err(|errors| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "message": "Invalid page content", "errors": errors })),
+        )
+    })?;
+
+    sqlx::query!(
+        "UPDATE pages SET content = ?, content_text = ? WHERE id = ? AND deleted_at IS NULL",
+        sqlx::types::Json(&content),
+        plain_text(&content),
+        payload.id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let mentioned = sync_references(&mut tx, &payload.id, &content, &user.id)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    notify_mentions(&pool, &mentioned, &workspace_id, &payload.id, &user.id).await;
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.updated",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: Some(before),
+            after: Some(content_summary(&content)),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &workspace_id,
+        WebhookEvent::PageUpdated,
+        json!({ "page_id": payload.id, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Page updated successfully", "content": content })),
+    ))
+}
+
+/// Audit kaydı için içeriğin kısa özeti; içeriğin kendisi loglanmaz.
+fn content_summary(content: &Value) -> Value {
+    json!({
+        "blocks": content.as_array().map_or(0, |blocks| blocks.len()),
+        "characters": plain_text(content).chars().count(),
+    })
+}
+
+/// Sayfada yeni bahsedilen kullanıcılara bildirim gönderir; commit'ten sonra çağrılır.
+async fn notify_mentions(
+    pool: &MySqlPool,
+    user_ids: &[String],
+    workspace_id: &str,
+    page_id: &str,
+    actor_id: &str,
+) {
+    for user_id in user_ids {
+        notify(
+            pool,
+            NewNotification {
+                user_id,
+                kind: NotificationKind::Mention,
+                actor_id: Some(actor_id),
+                workspace_id: Some(workspace_id),
+                page_id: Some(page_id),
+                data: json!({}),
+            },
+        )
+        .await;
+    }
+}
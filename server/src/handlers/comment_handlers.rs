@@ -0,0 +1,425 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::handlers::access::{page_workspace_id, require_member, workspace_role};
+use crate::models::user::User;
+
+const MAX_COMMENT_LENGTH: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    pub page_id: String,
+    pub parent_id: Option<String>,
+    pub block_id: Option<String>,
+    pub author_id: String,
+    pub author_username: String,
+    pub author_avatar: String,
+    pub body: String,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Thread {
+    #[serde(flatten)]
+    pub comment: Comment,
+    pub replies: Vec<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentListParams {
+    #[serde(default)]
+    pub include_resolved: bool,
+}
+
+/// GET /api/pages/:id/comments — kök yorumlar ve yanıtları, eskiden yeniye
+pub async fn get_comments(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(page_id): Path<String>,
+    Query(params): Query<CommentListParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        ));
+    }
+
+    let comments = sqlx::query_as!(
+        Comment,
+        "
+        SELECT c.id, c.page_id, c.parent_id, c.block_id, c.author_id,
+               u.username as author_username, u.avatar as author_avatar,
+               c.body, c.resolved_at, c.resolved_by, c.created_at, c.updated_at
+        FROM page_comments c
+        INNER JOIN users u ON u.id = c.author_id
+        WHERE c.page_id = ?
+        ORDER BY c.created_at, c.id
+        ",
+        page_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let (roots, replies): (Vec<Comment>, Vec<Comment>) =
+        comments.into_iter().partition(|c| c.parent_id.is_none());
+
+    let mut threads: Vec<Thread> = roots
+        .into_iter()
+        .filter(|c| params.include_resolved || c.resolved_at.is_none())
+        .map(|comment| Thread { comment, replies: Vec::new() })
+        .collect();
+
+    for reply in replies {
+        if let Some(thread) = threads
+            .iter_mut()
+            .find(|t| Some(&t.comment.id) == reply.parent_id.as_ref())
+        {
+            thread.replies.push(reply);
+        }
+    }
+
+    Ok((StatusCode::OK, Json(threads)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateCommentPayload {
+    pub page_id: String,
+    pub body: String,
+    /// Yorumun bağlı olduğu blok (opsiyonel)
+    #[serde(default)]
+    pub block_id: Option<String>,
+    /// Yanıt verilen yorum; yanıtlar her zaman başlığın kök yorumuna bağlanır
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+pub async fn create_comment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CreateCommentPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &payload.page_id).await?;
+    require_commenter(&pool, &workspace_id, &user.id).await?;
+    let body = check_body(&payload.body)?;
+
+    let (parent_id, block_id) = match &payload.parent_id {
+        Some(parent_id) => {
+            let parent = fetch_comment(&pool, parent_id).await?;
+            if parent.page_id != payload.page_id {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({ "message": "Comment belongs to another page"})),
+                ));
+            }
+            // Yanıtlar başlığın bloğunu devralır
+            (Some(parent.parent_id.unwrap_or(parent.id)), parent.block_id)
+        }
+        None => (None, payload.block_id.clone()),
+    };
+
+    let comment_id = Uuid::new_v4().to_string();
+    sqlx::query!(
+        "INSERT INTO page_comments (id, page_id, parent_id, block_id, author_id, body) VALUES (?, ?, ?, ?, ?, ?)",
+        comment_id,
+        payload.page_id,
+        parent_id,
+        block_id,
+        user.id,
+        body
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Comment created successfully", "comment_id": comment_id })),
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EditCommentPayload {
+    pub id: String,
+    pub body: String,
+}
+
+pub async fn edit_comment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<EditCommentPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let comment = fetch_comment(&pool, &payload.id).await?;
+    // Workspace'ten çıkarılan kullanıcı eski yorumlarını da değiştiremez
+    let workspace_id = page_workspace_id(&pool, &comment.page_id).await?;
+    require_member(&pool, &workspace_id, &user.id).await?;
+    require_author(&comment, &user)?;
+    let body = check_body(&payload.body)?;
+
+    sqlx::query!(
+        "UPDATE page_comments SET body = ? WHERE id = ?",
+        body,
+        payload.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Comment updated successfully" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentIdPayload {
+    pub id: String,
+}
+
+/// Kök yorum silinirse başlıktaki tüm yanıtlar da silinir.
+pub async fn delete_comment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CommentIdPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let comment = fetch_comment(&pool, &payload.id).await?;
+    // Workspace'ten çıkarılan kullanıcı eski yorumlarını da değiştiremez
+    let workspace_id = page_workspace_id(&pool, &comment.page_id).await?;
+    require_member(&pool, &workspace_id, &user.id).await?;
+    require_author(&comment, &user)?;
+
+    sqlx::query!("DELETE FROM page_comments WHERE id = ?", payload.id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Comment deleted successfully" }))))
+}
+
+pub async fn resolve_comment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CommentIdPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let thread_id = thread_for_update(&pool, &payload.id, &user).await?;
+
+    sqlx::query!(
+        "UPDATE page_comments SET resolved_at = NOW(), resolved_by = ? WHERE id = ? AND resolved_at IS NULL",
+        user.id,
+        thread_id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Thread resolved" }))))
+}
+
+pub async fn reopen_comment(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CommentIdPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let thread_id = thread_for_update(&pool, &payload.id, &user).await?;
+
+    sqlx::query!(
+        "UPDATE page_comments SET resolved_at = NULL, resolved_by = NULL WHERE id = ?",
+        thread_id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Thread reopened" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommentSettingsPayload {
+    pub workspace_id: String,
+    pub allow_viewer_comments: bool,
+}
+
+/// Sadece workspace sahibi izleyicilerin yorum yapmasına izin verebilir.
+pub async fn update_comment_settings(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CommentSettingsPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match workspace_role(&pool, &payload.workspace_id, &user.id).await? {
+        Some(role) if role == "owner" => {}
+        _ => {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(json!({ "message": "Only the workspace owner can change comment settings"})),
+            ))
+        }
+    }
+
+    sqlx::query!(
+        "UPDATE workspaces SET allow_viewer_comments = ? WHERE id = ?",
+        payload.allow_viewer_comments,
+        payload.workspace_id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Comment settings updated" }))))
+}
+
+/// Owner ve editor her zaman, viewer ise workspace izin veriyorsa yorum yapabilir.
+async fn require_commenter(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    user_id: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    match workspace_role(pool, workspace_id, user_id).await? {
+        Some(role) if role == "viewer" => {
+            let workspace = sqlx::query!(
+                r#"SELECT allow_viewer_comments as "allow_viewer_comments: bool" FROM workspaces WHERE id = ?"#,
+                workspace_id
+            )
+            .fetch_one(pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error"})),
+                )
+            })?;
+
+            if workspace.allow_viewer_comments {
+                Ok(())
+            } else {
+                Err((
+                    StatusCode::FORBIDDEN,
+                    Json(json!({ "message": "Viewers cannot comment in this workspace"})),
+                ))
+            }
+        }
+        Some(_) => Ok(()),
+        None => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        )),
+    }
+}
+
+/// Çözme/yeniden açma başlığın kök yorumuna uygulanır; yorum yapabilen herkes yapabilir.
+async fn thread_for_update(
+    pool: &MySqlPool,
+    comment_id: &str,
+    user: &User,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    let comment = fetch_comment(pool, comment_id).await?;
+    let workspace_id = page_workspace_id(pool, &comment.page_id).await?;
+    require_commenter(pool, &workspace_id, &user.id).await?;
+    Ok(comment.parent_id.unwrap_or(comment.id))
+}
+
+struct CommentRef {
+    id: String,
+    page_id: String,
+    parent_id: Option<String>,
+    block_id: Option<String>,
+    author_id: String,
+}
+
+async fn fetch_comment(
+    pool: &MySqlPool,
+    comment_id: &str,
+) -> Result<CommentRef, (StatusCode, Json<Value>)> {
+    sqlx::query_as!(
+        CommentRef,
+        "
+        SELECT c.id, c.page_id, c.parent_id, c.block_id, c.author_id
+        FROM page_comments c
+        INNER JOIN pages p ON p.id = c.page_id
+        WHERE c.id = ? AND p.deleted_at IS NULL
+        ",
+        comment_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Comment not found"})),
+        )
+    })
+}
+
+fn require_author(comment: &CommentRef, user: &User) -> Result<(), (StatusCode, Json<Value>)> {
+    if comment.author_id == user.id {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "Only the author can change this comment"})),
+        ))
+    }
+}
+
+fn check_body(body: &str) -> Result<&str, (StatusCode, Json<Value>)> {
+    let body = body.trim();
+    if body.is_empty() || body.len() > MAX_COMMENT_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "Comment must be between 1 and 10000 characters"})),
+        ));
+    }
+    Ok(body)
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+use tokio::sync::mpsc;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::content::export::{
+    encode_path_segment, export_document, page_paths, sanitize_file_name,
+    ExportFormat, LinkContext,
+};
+use crate::handlers::access::{page_workspace_id, require_member};
+use crate::models::user::User;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportParams {
+    #[serde(default = "default_format")]
+    pub format: ExportFormat,
+}
+
+fn default_format() -> ExportFormat {
+    ExportFormat::Markdown
+}
+
+/// GET /api/pages/:id/export?format=markdown|html
+/// Diğer sayfalara bağlantılar tek dosyada çözülemeyeceği için sadece başlık olarak yazılır.
+pub async fn export_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(page_id): Path<String>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    require_member(&pool, &workspace_id, &user.id).await?;
+
+    let page = sqlx::query!(
+        r#"SELECT title, content as "content: sqlx::types::Json<Value>" FROM pages WHERE id = ?"#,
+        page_id
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let mut links = LinkContext::default();
+    let targets = sqlx::query!(
+        "
+        SELECT DISTINCT p.id, p.title
+        FROM page_links l
+        INNER JOIN pages p ON p.id = l.target_page_id
+        WHERE l.source_page_id = ? AND p.workspace_id = ? AND p.deleted_at IS NULL
+        ",
+        page_id,
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+    for target in targets {
+        links.add_page(&target.id, &target.title, None);
+    }
+    add_mentioned_users(&pool, &workspace_id, Some(&page_id), &mut links).await?;
+
+    let document = export_document(page.content.as_ref().map(|c| &c.0));
+    let body = params.format.render(&page.title, &document, &links);
+    let file_name = format!("{}.{}", sanitize_file_name(&page.title), params.format.extension());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, params.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, content_disposition("attachment", &file_name)),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// GET /api/workspaces/:id/export?format=markdown|html
+/// Sayfa ağacını klasörler halinde ZIP olarak akıtır; sayfalar arası bağlantılar göreli yoldur.
+pub async fn export_workspace(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(workspace_id): Path<String>,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, (StatusCode, Json<serde_json::Value>)> {
+    require_member(&pool, &workspace_id, &user.id).await?;
+
+    let workspace = sqlx::query!("SELECT name FROM workspaces WHERE id = ?", workspace_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT id, title, parent_id, content as "content: sqlx::types::Json<Value>"
+        FROM pages
+        WHERE workspace_id = ? AND deleted_at IS NULL
+        ORDER BY title, created_at
+        "#,
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    // Yol üretimi ebeveynlerin önce gelmesini bekler: ağacı kökten başlayarak sırala
+    let known: HashMap<&str, usize> = rows.iter().enumerate().map(|(i, r)| (r.id.as_str(), i)).collect();
+    let mut children: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
+    for (i, row) in rows.iter().enumerate() {
+        let parent = row.parent_id.as_deref().filter(|p| known.contains_key(p));
+        children.entry(parent).or_default().push(i);
+    }
+    let mut order = Vec::with_capacity(rows.len());
+    let mut stack: Vec<usize> = children.get(&None).cloned().unwrap_or_default();
+    stack.reverse();
+    while let Some(i) = stack.pop() {
+        order.push(i);
+        if let Some(kids) = children.get(&Some(rows[i].id.as_str())) {
+            stack.extend(kids.iter().rev());
+        }
+    }
+
+    let tree: Vec<(String, String, Option<String>)> = order
+        .iter()
+        .map(|&i| (rows[i].id.clone(), rows[i].title.clone(), rows[i].parent_id.clone()))
+        .collect();
+    let paths = page_paths(&tree, params.format);
+
+    let mut links = LinkContext::default();
+    for (id, title, _) in &tree {
+        links.add_page(id, title, paths.get(id).cloned());
+    }
+    add_mentioned_users(&pool, &workspace_id, None, &mut links).await?;
+
+    let format = params.format;
+    let mut rows = rows;
+    let pages: Vec<(String, String, Option<Value>)> = order
+        .into_iter()
+        .map(|i| (rows[i].id.clone(), rows[i].title.clone(), rows[i].content.take().map(|c| c.0)))
+        .collect();
+
+    // ZIP, engelleyen bir iş parçacığında yazılır ve parça parça yanıt gövdesine aktarılır
+    let (tx, rx) = mpsc::channel::<Result<Bytes, std::io::Error>>(16);
+    tokio::task::spawn_blocking(move || {
+        let mut zip = ZipWriter::new_stream(ChannelWriter { tx: tx.clone() });
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        let result = (|| -> zip::result::ZipResult<()> {
+            for (id, title, content) in &pages {
+                let path = &paths[id];
+                links.set_base(path);
+
+                let document = export_document(content.as_ref());
+                zip.start_file(path.as_str(), options)?;
+                zip.write_all(format.render(title, &document, &links).as_bytes())?;
+            }
+            zip.finish()?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            tracing::error!(error = %e, "workspace export failed");
+            let _ = tx.blocking_send(Err(std::io::Error::other(e.to_string())));
+        }
+    });
+
+    let stream = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    });
+    let file_name = format!("{}.zip", sanitize_file_name(&workspace.name));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (header::CONTENT_DISPOSITION, content_disposition("attachment", &file_name)),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response())
+}
+
+/// ZIP yazıcısının çıktısını yanıt akışına gönderen köprü; istemci bağlantıyı keserse yazma hata verir.
+struct ChannelWriter {
+    tx: mpsc::Sender<Result<Bytes, std::io::Error>>,
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.tx
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client disconnected"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `attachment` ya da `inline` Content-Disposition değeri; ASCII olmayan adlar için
+/// RFC 5987 biçimi de eklenir.
+pub fn content_disposition(disposition: &str, file_name: &str) -> String {
+    let ascii: String = file_name
+        .chars()
+        .map(|c| if c.is_ascii() && c != '"' { c } else { '_' })
+        .collect();
+    format!(
+        "{}; filename=\"{}\"; filename*=UTF-8''{}",
+        disposition,
+        ascii,
+        encode_path_segment(file_name)
+    )
+}
+
+/// Bahsedilen kullanıcıların adlarını yükler; `page_id` verilmezse bütün workspace için.
+async fn add_mentioned_users(
+    pool: &MySqlPool,
+    workspace_id: &str,
+    page_id: Option<&str>,
+    links: &mut LinkContext,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let users = sqlx::query!(
+        "
+        SELECT DISTINCT u.id, u.username
+        FROM page_mentions m
+        INNER JOIN pages p ON p.id = m.page_id
+        INNER JOIN users u ON u.id = m.user_id
+        INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id AND wm.user_id = m.user_id
+        WHERE p.workspace_id = ? AND (? IS NULL OR m.page_id = ?)
+        ",
+        workspace_id,
+        page_id,
+        page_id
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    for user in users {
+        links.add_user(&user.id, &user.username);
+    }
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use axum::{
+    extract::Multipart,
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::content::import::{
+    build_pages, is_zip, link_pages, ImportError, ImportFile, ImportFiles,
+};
+use crate::content::links::sync_references;
+use crate::content::search::plain_text;
+use crate::handlers::access::require_editor;
+use crate::handlers::page_tree::check_parent;
+use crate::models::user::User;
+use crate::webhooks::{dispatch, WebhookEvent};
+
+/// POST /api/import (multipart)
+/// Alanlar: `workspace_id`, isteğe bağlı `parent_id` ve bir ya da daha fazla `file`
+/// (.md, .html ya da bunları içeren .zip — Notion'ın Markdown & CSV dışa aktarımı dahil).
+/// Arşivdeki klasör yapısı sayfa ağacı olarak, `parent_id` altında yeniden kurulur.
+pub async fn import_pages(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let mut workspace_id = None;
+    let mut parent_id = None;
+    let mut uploads: Vec<ImportFile> = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+        )
+    })? {
+        let name = field.name().unwrap_or("").to_string();
+        let file_name = field.file_name().map(str::to_string);
+        let data = field.bytes().await.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "message": format!("Invalid upload: {}", e.body_text()) })),
+            )
+        })?;
+
+        match name.as_str() {
+            "workspace_id" => workspace_id = Some(String::from_utf8_lossy(&data).trim().to_string()),
+            "parent_id" => {
+                parent_id = Some(String::from_utf8_lossy(&data).trim().to_string())
+                    .filter(|p| !p.is_empty())
+            }
+            "file" => uploads.push(ImportFile {
+                path: file_name.unwrap_or_else(|| "Untitled.md".to_string()),
+                data: data.to_vec(),
+            }),
+            _ => {}
+        }
+    }
+
+    let workspace_id = workspace_id.filter(|w| !w.is_empty()).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "workspace_id is required"})),
+        )
+    })?;
+    if uploads.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "At least one file is required"})),
+        ));
+    }
+
+    require_editor(&pool, &workspace_id, &user.id, "Viewers cannot import pages").await?;
+    if let Some(parent_id) = &parent_id {
+        check_parent(&pool, parent_id, &workspace_id).await?;
+    }
+
+    // Arşiv açma ve dönüştürme CPU'ya bağlı olduğu için engelleyen iş parçacığında yapılır
+    let mut pages = tokio::task::spawn_blocking(move || -> Result<_, ImportError> {
+        // Sınırlar yüklemelerin hepsi için birlikte sayılır
+        let mut files = ImportFiles::new();
+        for upload in uploads {
+            if is_zip(&upload.path) {
+                files.add_zip(&upload.data)?;
+            } else {
+                files.add_file(&upload.path, upload.data)?;
+            }
+        }
+        Ok(build_pages(files.into_files()))
+    })
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Import failed"})),
+        )
+    })?
+    .map_err(|e| {
+        let status = match e {
+            ImportError::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::BAD_REQUEST,
+        };
+        (status, Json(json!({ "message": e.to_string() })))
+    })?;
+
+    if pages.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "No Markdown or HTML files found"})),
+        ));
+    }
+
+    let ids: Vec<String> = pages.iter().map(|_| Uuid::new_v4().to_string()).collect();
+    link_pages(&mut pages, &ids);
+
+    let contents: Vec<Value> = pages
+        .iter()
+        .map(|page| serde_json::to_value(&page.document).unwrap_or_else(|_| json!([])))
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    for (i, page) in pages.iter().enumerate() {
+        let page_parent = match page.parent {
+            Some(index) => Some(ids[index].clone()),
+            None => parent_id.clone(),
+        };
+        sqlx::query!(
+            "INSERT INTO pages (id, title, workspace_id, parent_id, content, content_text, created_by) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            ids[i],
+            page.title,
+            workspace_id,
+            page_parent,
+            sqlx::types::Json(&contents[i]),
+            plain_text(&contents[i]),
+            user.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error"})),
+            )
+        })?;
+    }
+
+    // Bağlantılar, hedef sayfaların hepsi eklendikten sonra kaydedilir
+    for (id, content) in ids.iter().zip(&contents) {
+        sync_references(&mut tx, id, content, &user.id)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+    }
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let root_ids: Vec<&String> = pages
+        .iter()
+        .zip(&ids)
+        .filter(|(page, _)| page.parent.is_none())
+        .map(|(_, id)| id)
+        .collect();
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "pages.imported",
+            target_type: "workspace",
+            target_id: Some(&workspace_id),
+            before: None,
+            after: Some(json!({ "pages": ids.len(), "root_page_ids": root_ids, "parent_id": parent_id })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    for (page, id) in pages.iter().zip(&ids) {
+        let page_parent = page.parent.map(|index| ids[index].clone()).or(parent_id.clone());
+        dispatch(
+            &pool,
+            &workspace_id,
+            WebhookEvent::PageCreated,
+            json!({ "page_id": id, "title": page.title, "parent_id": page_parent, "actor_id": user.id, "imported": true }),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Pages imported successfully",
+        "pages_created": ids.len(),
+        "page_ids": root_ids
+         })),
+    ))
+}
@@ -0,0 +1,147 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use crate::handlers::access::{page_workspace_id, workspace_role};
+use crate::models::user::User;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedPage {
+    pub id: String,
+    pub title: String,
+    pub workspace_id: String,
+}
+
+/// GET /api/pages/:id/backlinks — bu sayfaya bağlantı veren sayfalar.
+/// Başlıklar her istekte sayfalardan okunduğu için yeniden adlandırmalar hemen yansır;
+/// çöpteki veya kullanıcının erişemediği kaynak sayfalar listelenmez.
+pub async fn get_backlinks(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(page_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        ));
+    }
+
+    let pages = sqlx::query_as!(
+        LinkedPage,
+        "
+        SELECT DISTINCT p.id, p.title, p.workspace_id
+        FROM page_links l
+        INNER JOIN pages p ON p.id = l.source_page_id
+        INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id AND wm.user_id = ?
+        WHERE l.target_page_id = ? AND p.deleted_at IS NULL
+        ORDER BY p.title
+        ",
+        user.id,
+        page_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(pages)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutgoingLink {
+    pub block_id: String,
+    pub page_id: String,
+    pub title: String,
+    pub trashed: bool,
+}
+
+/// GET /api/pages/:id/links — sayfadaki bağlantıların güncel başlıkları.
+/// Hedef sayfa çöpteyse `trashed` true döner; kalıcı olarak silinen ya da başka bir
+/// workspace'e taşınan hedefler listeden düşer.
+pub async fn get_page_links(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(page_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &page_id).await?;
+    if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "You don't have access to this workspace"})),
+        ));
+    }
+
+    let links = sqlx::query_as!(
+        OutgoingLink,
+        r#"
+        SELECT l.block_id, p.id as page_id, p.title,
+               (p.deleted_at IS NOT NULL) as "trashed!: bool"
+        FROM page_links l
+        INNER JOIN pages p ON p.id = l.target_page_id
+        WHERE l.source_page_id = ? AND p.workspace_id = ?
+        "#,
+        page_id,
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(links)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Mention {
+    pub page_id: String,
+    pub title: String,
+    pub workspace_id: String,
+    pub block_id: String,
+    pub mentioned_by: Option<String>,
+    pub mentioned_by_username: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// GET /api/mentions — kullanıcının bahsedildiği sayfalar, en yeniden eskiye
+pub async fn get_my_mentions(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let mentions = sqlx::query_as!(
+        Mention,
+        r#"
+        SELECT m.page_id, p.title, p.workspace_id, m.block_id, m.mentioned_by,
+               u.username as "mentioned_by_username?", m.created_at
+        FROM page_mentions m
+        INNER JOIN pages p ON p.id = m.page_id
+        INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id AND wm.user_id = m.user_id
+        LEFT JOIN users u ON u.id = m.mentioned_by
+        WHERE m.user_id = ? AND p.deleted_at IS NULL
+        ORDER BY m.created_at DESC
+        LIMIT 100
+        "#,
+        user.id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(mentions)))
+}
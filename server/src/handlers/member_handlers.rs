@@ -0,0 +1,288 @@
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::handlers::access::{require_owner, workspace_role};
+use crate::models::user::User;
+use crate::notifications::{notify, NewNotification, NotificationKind};
+use crate::webhooks::{dispatch, WebhookEvent};
+
+const MANAGE_MEMBERS_MESSAGE: &str = "Only the workspace owner can manage members";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AddMemberPayload {
+    pub workspace_id: String,
+    pub email: String,
+    pub role: String,
+}
+
+pub async fn add_member(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<AddMemberPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_owner(&pool, &payload.workspace_id, &user.id, MANAGE_MEMBERS_MESSAGE).await?;
+    check_role(&payload.role)?;
+
+    let member = sqlx::query!("SELECT id FROM users WHERE email = ?", payload.email)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "message": "User not found" })),
+            )
+        })?;
+
+    if workspace_role(&pool, &payload.workspace_id, &member.id).await?.is_some() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "User is already a member of this workspace" })),
+        ));
+    }
+
+    sqlx::query!(
+        "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES (?, ?, ?)",
+        payload.workspace_id,
+        member.id,
+        payload.role
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Failed to add workspace member" })),
+        )
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&payload.workspace_id),
+            actor_id: Some(&user.id),
+            action: "member.added",
+            target_type: "user",
+            target_id: Some(&member.id),
+            before: None,
+            after: Some(json!({ "email": payload.email, "role": payload.role })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    notify(
+        &pool,
+        NewNotification {
+            user_id: &member.id,
+            kind: NotificationKind::WorkspaceInvite,
+            actor_id: Some(&user.id),
+            workspace_id: Some(&payload.workspace_id),
+            page_id: None,
+            data: json!({ "role": payload.role }),
+        },
+    )
+    .await;
+
+    dispatch(
+        &pool,
+        &payload.workspace_id,
+        WebhookEvent::MemberAdded,
+        json!({ "user_id": member.id, "role": payload.role, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Member added successfully" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveMemberPayload {
+    pub workspace_id: String,
+    pub user_id: String,
+}
+
+/// Owner başka üyeleri çıkarabilir; diğer üyeler sadece kendileri ayrılabilir.
+pub async fn remove_member(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<RemoveMemberPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let target_role = workspace_role(&pool, &payload.workspace_id, &payload.user_id)
+        .await?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({ "message": "Member not found" })),
+            )
+        })?;
+
+    if target_role == "owner" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "The workspace owner cannot be removed" })),
+        ));
+    }
+    if payload.user_id != user.id {
+        require_owner(&pool, &payload.workspace_id, &user.id, MANAGE_MEMBERS_MESSAGE).await?;
+    }
+
+    sqlx::query!(
+        "DELETE FROM workspace_members WHERE workspace_id = ? AND user_id = ?",
+        payload.workspace_id,
+        payload.user_id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&payload.workspace_id),
+            actor_id: Some(&user.id),
+            action: "member.removed",
+            target_type: "user",
+            target_id: Some(&payload.user_id),
+            before: Some(json!({ "role": target_role })),
+            after: None,
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    if payload.user_id != user.id {
+        notify(
+            &pool,
+            NewNotification {
+                user_id: &payload.user_id,
+                kind: NotificationKind::MemberRemoved,
+                actor_id: Some(&user.id),
+                workspace_id: Some(&payload.workspace_id),
+                page_id: None,
+                data: json!({}),
+            },
+        )
+        .await;
+    }
+
+    dispatch(
+        &pool,
+        &payload.workspace_id,
+        WebhookEvent::MemberRemoved,
+        json!({ "user_id": payload.user_id, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Member removed successfully" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateMemberRolePayload {
+    pub workspace_id: String,
+    pub user_id: String,
+    pub role: String,
+}
+
+pub async fn update_member_role(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<UpdateMemberRolePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_owner(&pool, &payload.workspace_id, &user.id, MANAGE_MEMBERS_MESSAGE).await?;
+    check_role(&payload.role)?;
+
+    let previous_role = match workspace_role(&pool, &payload.workspace_id, &payload.user_id).await? {
+        Some(role) if role == "owner" => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "message": "The owner's role cannot be changed" })),
+            ))
+        }
+        Some(role) if role == payload.role => {
+            return Ok((StatusCode::OK, Json(json!({ "message": "Member role updated successfully" }))))
+        }
+        Some(role) => role,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({ "message": "Member not found" })),
+            ))
+        }
+    };
+
+    sqlx::query!(
+        "UPDATE workspace_members SET role = ? WHERE workspace_id = ? AND user_id = ?",
+        payload.role,
+        payload.workspace_id,
+        payload.user_id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&payload.workspace_id),
+            actor_id: Some(&user.id),
+            action: "member.role_changed",
+            target_type: "user",
+            target_id: Some(&payload.user_id),
+            before: Some(json!({ "role": previous_role })),
+            after: Some(json!({ "role": payload.role })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    notify(
+        &pool,
+        NewNotification {
+            user_id: &payload.user_id,
+            kind: NotificationKind::RoleChanged,
+            actor_id: Some(&user.id),
+            workspace_id: Some(&payload.workspace_id),
+            page_id: None,
+            data: json!({ "role": payload.role }),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &payload.workspace_id,
+        WebhookEvent::MemberRoleChanged,
+        json!({ "user_id": payload.user_id, "role": payload.role, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Member role updated successfully" }))))
+}
+
+/// Üyelere sadece editor veya viewer rolü verilebilir; her workspace'in tek owner'ı vardır.
+fn check_role(role: &str) -> Result<(), (StatusCode, Json<Value>)> {
+    match role {
+        "editor" | "viewer" => Ok(()),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "Role must be editor or viewer" })),
+        )),
+    }
+}
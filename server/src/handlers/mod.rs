@@ -1,4 +1,19 @@
+pub mod access;
+pub mod attachment_handlers;
+pub mod audit_handlers;
 pub mod auth_handlers;
+pub mod avatar_handlers;
 pub mod jwt;
 pub mod body_handlers;
+pub mod comment_handlers;
+pub mod export_handlers;
+pub mod import_handlers;
+pub mod link_handlers;
+pub mod member_handlers;
+pub mod notification_handlers;
+pub mod page_tree;
+pub mod search_handlers;
 pub mod service_handler;
+pub mod trash_handlers;
+pub mod template_handlers;
+pub mod webhook_handlers;
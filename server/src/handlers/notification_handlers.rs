@@ -0,0 +1,133 @@
+use axum::{extract::Query, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+
+use crate::models::user::User;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    pub kind: String,
+    pub actor_id: Option<String>,
+    pub actor_username: Option<String>,
+    pub workspace_id: Option<String>,
+    pub page_id: Option<String>,
+    pub data: Option<sqlx::types::Json<Value>>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationListParams {
+    #[serde(default)]
+    pub unread_only: bool,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// GET /api/notifications?unread_only=true&limit=50
+pub async fn get_notifications(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Query(params): Query<NotificationListParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+
+    let notifications = sqlx::query_as!(
+        Notification,
+        r#"
+        SELECT n.id, n.kind, n.actor_id, u.username as "actor_username?",
+               n.workspace_id, n.page_id,
+               n.data as "data: sqlx::types::Json<Value>",
+               n.read_at, n.created_at
+        FROM notifications n
+        LEFT JOIN users u ON u.id = n.actor_id
+        WHERE n.user_id = ? AND (NOT ? OR n.read_at IS NULL)
+        ORDER BY n.created_at DESC
+        LIMIT ?
+        "#,
+        user.id,
+        params.unread_only,
+        limit
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let unread = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!: i64" FROM notifications WHERE user_id = ? AND read_at IS NULL"#,
+        user.id
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "notifications": notifications,
+            "unread_count": unread.count,
+        })),
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkReadPayload {
+    pub ids: Vec<String>,
+}
+
+pub async fn mark_notifications_read(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<MarkReadPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    for id in &payload.ids {
+        sqlx::query!(
+            "UPDATE notifications SET read_at = NOW() WHERE id = ? AND user_id = ? AND read_at IS NULL",
+            id,
+            user.id
+        )
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+    }
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Notifications marked as read" }))))
+}
+
+pub async fn mark_all_notifications_read(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    sqlx::query!(
+        "UPDATE notifications SET read_at = NOW() WHERE user_id = ? AND read_at IS NULL",
+        user.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "All notifications marked as read" }))))
+}
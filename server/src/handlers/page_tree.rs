@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{MySql, MySqlConnection, MySqlPool};
+use uuid::Uuid;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::handlers::access::{page_workspace_id, require_editor};
+use crate::models::user::User;
+use crate::storage::{SharedStorage, StorageError};
+use crate::webhooks::{dispatch, WebhookEvent};
+
+/// Sayfanın kendisi ve tüm alt sayfalarının id'leri; ebeveynler çocuklarından önce gelir.
+/// `include_deleted` false ise çöp kutusundaki alt sayfalar (ve altları) atlanır.
+pub async fn subtree_ids(
+    conn: &mut MySqlConnection,
+    root_id: &str,
+    include_deleted: bool,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        WITH RECURSIVE subtree (id, depth) AS (
+            SELECT id, 0 FROM pages WHERE id = ?
+            UNION ALL
+            SELECT p.id, s.depth + 1
+            FROM pages p
+            INNER JOIN subtree s ON p.parent_id = s.id
+            WHERE ? OR p.deleted_at IS NULL
+        )
+        SELECT id as "id!: String" FROM subtree ORDER BY depth
+        "#,
+        root_id,
+        include_deleted
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| r.id).collect())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatePagePayload {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub include_children: bool,
+}
+
+pub async fn duplicate_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<DuplicatePagePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = page_workspace_id(&pool, &payload.id).await?;
+    require_editor(&pool, &workspace_id, &user.id, "Viewers cannot duplicate pages").await?;
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let source_ids = if payload.include_children {
+        subtree_ids(&mut tx, &payload.id, false).await.map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?
+    } else {
+        vec![payload.id.clone()]
+    };
+
+    // Eski id → yeni id; ebeveynler önce kopyalandığı için parent her zaman eşlenmiş olur
+    let mut copies: HashMap<String, String> = HashMap::new();
+    // Commit'ten önce depolamaya yazılacak dosya kopyaları
+    let mut file_copies: Vec<FileCopy> = Vec::new();
+    let mut copy_title = String::new();
+    for source_id in &source_ids {
+        let new_id = Uuid::new_v4().to_string();
+        let is_root = *source_id == payload.id;
+
+        let source = sqlx::query!(
+            "SELECT title, parent_id FROM pages WHERE id = ?",
+            source_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        let (title, parent_id) = if is_root {
+            let title = payload
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("{} (copy)", source.title));
+            copy_title = title.clone();
+            (title, source.parent_id)
+        } else {
+            let parent_id = source.parent_id.and_then(|p| copies.get(&p).cloned());
+            (source.title, parent_id)
+        };
+
+        sqlx::query!(
+            "INSERT INTO pages (id, title, workspace_id, parent_id, content, content_text, created_by)
+             SELECT ?, ?, workspace_id, ?, content, content_text, ? FROM pages WHERE id = ?",
+            new_id,
+            title,
+            parent_id,
+            user.id,
+            source_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        // Bağlantı ve bahis kayıtları içerikle birlikte kopyalanır
+        sqlx::query!(
+            "INSERT INTO page_links (source_page_id, target_page_id, block_id)
+             SELECT ?, target_page_id, block_id FROM page_links WHERE source_page_id = ?",
+            new_id,
+            source_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        sqlx::query!(
+            "INSERT INTO page_mentions (page_id, user_id, block_id, mentioned_by)
+             SELECT ?, user_id, block_id, mentioned_by FROM page_mentions WHERE page_id = ?",
+            new_id,
+            source_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        // Ekler de kopyalanır; yoksa asıl sayfa kalıcı silinince kopyadaki dosyalar da silinir
+        let attachments = sqlx::query!(
+            "SELECT id, storage_key, content_type FROM attachments WHERE page_id = ?",
+            source_id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        for attachment in attachments {
+            let attachment_id = Uuid::new_v4().to_string();
+            let storage_key = format!("{}/{}", workspace_id, attachment_id);
+
+            sqlx::query!(
+                "INSERT INTO attachments (id, workspace_id, page_id, storage_key, file_name, content_type, size_bytes, uploaded_by)
+                 SELECT ?, workspace_id, ?, ?, file_name, content_type, size_bytes, uploaded_by FROM attachments WHERE id = ?",
+                attachment_id,
+                new_id,
+                storage_key,
+                attachment.id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+
+            sqlx::query!(
+                "UPDATE pages SET content = REPLACE(content, ?, ?) WHERE id = ?",
+                format!("/api/attachments/{}", attachment.id),
+                format!("/api/attachments/{}", attachment_id),
+                new_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+
+            file_copies.push(FileCopy {
+                from: attachment.storage_key,
+                to: storage_key,
+                content_type: attachment.content_type,
+            });
+        }
+
+        copies.insert(source_id.clone(), new_id);
+    }
+
+    copy_files(&storage, &file_copies).await.map_err(|e| {
+        tracing::error!(page_id = %payload.id, error = %e, "failed to copy attachments");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Failed to copy attachments" })),
+        )
+    })?;
+
+    if tx.commit().await.is_err() {
+        delete_files(&storage, &file_copies).await;
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        ));
+    }
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.duplicated",
+            target_type: "page",
+            target_id: Some(&copies[&payload.id]),
+            before: Some(json!({ "page_id": payload.id })),
+            after: Some(json!({ "title": copy_title, "pages_copied": copies.len() })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    dispatch(
+        &pool,
+        &workspace_id,
+        WebhookEvent::PageCreated,
+        json!({ "page_id": copies[&payload.id], "duplicated_from": payload.id, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "message": "Page duplicated successfully",
+            "page_id": copies[&payload.id],
+            "pages_copied": copies.len(),
+        })),
+    ))
+}
+
+struct FileCopy {
+    from: String,
+    to: String,
+    content_type: String,
+}
+
+/// Dosyaları yeni anahtarlarına kopyalar; biri başarısız olursa o ana kadar yazılanları siler.
+async fn copy_files(storage: &SharedStorage, files: &[FileCopy]) -> Result<(), StorageError> {
+    for (i, file) in files.iter().enumerate() {
+        let copied = match storage.get(&file.from).await {
+            Ok(data) => storage.put(&file.to, data, &file.content_type).await,
+            // Depolamada olmayan dosya kopyalanamaz; kayıt yine de oluşur, indirme 404 döner
+            Err(StorageError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = copied {
+            delete_files(storage, &files[..i]).await;
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+async fn delete_files(storage: &SharedStorage, files: &[FileCopy]) {
+    for file in files {
+        if let Err(e) = storage.delete(&file.to).await {
+            tracing::warn!(storage_key = %file.to, error = %e, "failed to delete copied attachment");
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MovePagePayload {
+    pub id: String,
+    pub workspace_id: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+pub async fn move_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<MovePagePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    // Kullanıcı hem kaynak hem hedef workspace'te düzenleme yetkisine sahip olmalı
+    let source_workspace_id = page_workspace_id(&pool, &payload.id).await?;
+    require_editor(&pool, &source_workspace_id, &user.id, "Viewers cannot move pages").await?;
+    if payload.workspace_id != source_workspace_id {
+        require_editor(&pool, &payload.workspace_id, &user.id, "Viewers cannot move pages").await?;
+    }
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let page = sqlx::query!("SELECT title, parent_id FROM pages WHERE id = ?", payload.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    // Alt sayfalar (çöptekiler dahil) sayfayla birlikte taşınır
+    let moved_ids = subtree_ids(&mut tx, &payload.id, true).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    if let Some(parent_id) = &payload.parent_id {
+        check_parent(&mut *tx, parent_id, &payload.workspace_id).await?;
+        if moved_ids.contains(parent_id) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "message": "A page cannot be moved under itself"})),
+            ));
+        }
+    }
+
+    for page_id in &moved_ids {
+        sqlx::query!(
+            "UPDATE pages SET workspace_id = ? WHERE id = ?",
+            payload.workspace_id,
+            page_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        // İndirme yetkisi eklerin workspace'ine göre denetlenir; ekler sayfayla birlikte taşınır
+        sqlx::query!(
+            "UPDATE attachments SET workspace_id = ? WHERE page_id = ?",
+            payload.workspace_id,
+            page_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+    }
+
+    sqlx::query!(
+        "UPDATE pages SET parent_id = ? WHERE id = ?",
+        payload.parent_id,
+        payload.id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    // Başka workspace'e taşımalar iki workspace'in etkinlik akışında da görünür
+    let mut audit_workspaces = vec![&source_workspace_id];
+    if payload.workspace_id != source_workspace_id {
+        audit_workspaces.push(&payload.workspace_id);
+    }
+    for workspace_id in audit_workspaces {
+        record(
+            &pool,
+            AuditEvent {
+                workspace_id: Some(workspace_id),
+                actor_id: Some(&user.id),
+                action: "page.moved",
+                target_type: "page",
+                target_id: Some(&payload.id),
+                before: Some(json!({
+                    "title": page.title,
+                    "workspace_id": source_workspace_id,
+                    "parent_id": page.parent_id,
+                })),
+                after: Some(json!({
+                    "workspace_id": payload.workspace_id,
+                    "parent_id": payload.parent_id,
+                    "moved_page_ids": moved_ids,
+                })),
+                ip: ip.as_deref(),
+            },
+        )
+        .await;
+    }
+
+    // Başka workspace'e taşınan sayfa, kaynak workspace'in abonelerine silinmiş,
+    // hedef workspace'in abonelerine oluşturulmuş olarak bildirilir
+    if payload.workspace_id != source_workspace_id {
+        dispatch(
+            &pool,
+            &source_workspace_id,
+            WebhookEvent::PageDeleted,
+            json!({
+                "page_id": payload.id,
+                "title": page.title,
+                "deleted_page_ids": moved_ids,
+                "moved_to_workspace_id": payload.workspace_id,
+                "actor_id": user.id,
+            }),
+        )
+        .await;
+        dispatch(
+            &pool,
+            &payload.workspace_id,
+            WebhookEvent::PageCreated,
+            json!({
+                "page_id": payload.id,
+                "title": page.title,
+                "parent_id": payload.parent_id,
+                "created_page_ids": moved_ids,
+                "moved_from_workspace_id": source_workspace_id,
+                "actor_id": user.id,
+            }),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "message": "Page moved successfully",
+            "pages_moved": moved_ids.len(),
+        })),
+    ))
+}
+
+/// Ebeveyn sayfanın var olduğunu, çöpte olmadığını ve verilen workspace'te olduğunu kontrol eder.
+pub async fn check_parent<'c, E>(
+    executor: E,
+    parent_id: &str,
+    workspace_id: &str,
+) -> Result<(), (StatusCode, Json<Value>)>
+where
+    E: sqlx::Executor<'c, Database = MySql>,
+{
+    let parent = sqlx::query!(
+        "SELECT workspace_id FROM pages WHERE id = ? AND deleted_at IS NULL",
+        parent_id
+    )
+    .fetch_optional(executor)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?;
+
+    match parent {
+        Some(p) if p.workspace_id == workspace_id => Ok(()),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "Parent page not found in this workspace"})),
+        )),
+    }
+}
@@ -0,0 +1,126 @@
+use axum::{extract::Query, http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::MySqlPool;
+
+use crate::content::search::{boolean_query, highlight, snippet};
+use crate::models::user::User;
+
+const SNIPPET_RADIUS: usize = 80;
+/// İstenebilecek en büyük sayfa numarası; ofset hesabının taşmaması için sınırlanır.
+const MAX_PAGE: u32 = 10_000;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    pub q: String,
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub page: Option<u32>,
+    #[serde(default)]
+    pub per_page: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResult {
+    pub id: String,
+    pub title: String,
+    pub workspace_id: String,
+    pub workspace_name: String,
+    pub title_highlight: String,
+    pub snippet: String,
+}
+
+/// GET /api/search?q=...&workspace_id=...&page=1&per_page=20
+/// Sadece kullanıcının üye olduğu workspace'lerdeki sayfalarda arar.
+pub async fn search_pages(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Query(params): Query<SearchParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let Some(query) = boolean_query(&params.q) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "message": "Search query is empty" })),
+        ));
+    };
+
+    let page = params.page.unwrap_or(1).clamp(1, MAX_PAGE);
+    let per_page = params.per_page.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * per_page;
+
+    let total = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "total!: i64"
+        FROM pages p
+        INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id AND wm.user_id = ?
+        WHERE p.deleted_at IS NULL
+          AND (? IS NULL OR p.workspace_id = ?)
+          AND MATCH(p.title, p.content_text) AGAINST (? IN BOOLEAN MODE)
+        "#,
+        user.id,
+        params.workspace_id,
+        params.workspace_id,
+        query
+    )
+    .fetch_one(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?
+    .total;
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT p.id, p.title, p.workspace_id, w.name as workspace_name, p.content_text
+        FROM pages p
+        INNER JOIN workspace_members wm ON wm.workspace_id = p.workspace_id AND wm.user_id = ?
+        INNER JOIN workspaces w ON w.id = p.workspace_id
+        WHERE p.deleted_at IS NULL
+          AND (? IS NULL OR p.workspace_id = ?)
+          AND MATCH(p.title, p.content_text) AGAINST (? IN BOOLEAN MODE)
+        ORDER BY MATCH(p.title, p.content_text) AGAINST (? IN BOOLEAN MODE) DESC, p.updated_at DESC
+        LIMIT ? OFFSET ?
+        "#,
+        user.id,
+        params.workspace_id,
+        params.workspace_id,
+        query,
+        query,
+        per_page,
+        offset
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let results: Vec<SearchResult> = rows
+        .into_iter()
+        .map(|row| SearchResult {
+            title_highlight: highlight(&row.title, &params.q),
+            snippet: snippet(row.content_text.as_deref().unwrap_or(""), &params.q, SNIPPET_RADIUS),
+            id: row.id,
+            title: row.title,
+            workspace_id: row.workspace_id,
+            workspace_name: row.workspace_name,
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "results": results,
+            "total": total,
+            "page": page,
+            "per_page": per_page,
+        })),
+    ))
+}
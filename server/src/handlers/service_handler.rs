@@ -8,20 +8,133 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sqlx::MySqlPool;
+use tracing::Instrument;
 use std::process::Stdio;
 use tokio::process::Command;
 use uuid::Uuid;
 
 use scraper_service_cli::{
     scraper::Scraper,
+    config::ScraperConfig,
+    scope::{CrawlScope, SubdomainPolicy},
+    report::CrawlReport,
     ai::AIAnalyzer,
 };
 
+use crate::config;
+use crate::models::user::User;
+use crate::notifications::{notify, NewNotification, NotificationKind};
 use crate::state::{AppState, JobStatus};
 
 #[derive(Deserialize)]
 pub struct ScrapeUrlPayload {
     pub url: String,
+    /// Verilmeyen alanlar varsayılanları alır (bkz. `ScraperConfig`)
+    #[serde(default)]
+    pub config: ScraperConfig,
+    /// Eski istemcilerin üst düzeyde gönderdiği kapsam alanları; `config.scope`'u ezer
+    #[serde(flatten)]
+    pub scope: ScopeFields,
+}
+
+/// max_depth, include, exclude, path_prefix, subdomains, skip_extensions
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct ScopeFields {
+    pub max_depth: Option<usize>,
+    pub include: Option<Vec<String>>,
+    pub exclude: Option<Vec<String>>,
+    pub path_prefix: Option<String>,
+    pub subdomains: Option<SubdomainPolicy>,
+    pub skip_extensions: Option<Vec<String>>,
+}
+
+impl ScopeFields {
+    fn apply(self, scope: &mut CrawlScope) {
+        if self.max_depth.is_some() {
+            scope.max_depth = self.max_depth;
+        }
+        if let Some(include) = self.include {
+            scope.include = include;
+        }
+        if let Some(exclude) = self.exclude {
+            scope.exclude = exclude;
+        }
+        if self.path_prefix.is_some() {
+            scope.path_prefix = self.path_prefix;
+        }
+        if let Some(subdomains) = self.subdomains {
+            scope.subdomains = subdomains;
+        }
+        if let Some(skip_extensions) = self.skip_extensions {
+            scope.skip_extensions = skip_extensions;
+        }
+    }
+}
+
+/// İstemcilerin değiştiremeyeceği başlıklar: isteğin yönünü ya da kaynağını değiştirirler
+const FORBIDDEN_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "content-length",
+    "transfer-encoding",
+    "te",
+    "upgrade",
+    "forwarded",
+    "proxy-authorization",
+];
+
+/// API'den gelen ayarları sunucu sınırlarına çeker. Sunucunun çıkış trafiğini istemciler
+/// yönlendiremez, robots.txt'yi yok sayamaz ve hedef sitelere sunucu ayarlarından daha sık
+/// ya da daha uzun süre istek gönderemez.
+fn restrict_config(scrape_config: &mut ScraperConfig) -> Result<(), String> {
+    if scrape_config.proxy.is_some() {
+        return Err("proxy is not allowed".to_string());
+    }
+    if let Some(name) = scrape_config.headers.keys().find(|name| {
+        let name = name.to_ascii_lowercase();
+        FORBIDDEN_HEADERS.contains(&name.as_str()) || name.starts_with("x-forwarded-")
+    }) {
+        return Err(format!("header '{}' is not allowed", name));
+    }
+
+    scrape_config.respect_robots = true;
+    scrape_config.max_pages = scrape_config.max_pages.min(config::scrape_max_pages());
+    scrape_config.max_frontier = scrape_config.max_frontier.min(config::scrape_max_frontier());
+    scrape_config.max_page_bytes = scrape_config
+        .max_page_bytes
+        .min(config::scrape_max_page_bytes());
+    let max_duration = config::scrape_max_duration_secs();
+    scrape_config.max_duration_secs = Some(
+        scrape_config
+            .max_duration_secs
+            .map_or(max_duration, |secs| secs.min(max_duration)),
+    );
+
+    scrape_config.concurrent_requests = scrape_config
+        .concurrent_requests
+        .min(config::scrape_max_concurrency());
+    scrape_config.max_concurrent_per_host = scrape_config
+        .max_concurrent_per_host
+        .min(config::scrape_max_concurrency());
+    scrape_config.crawl_delay_ms = scrape_config
+        .crawl_delay_ms
+        .max(config::scrape_min_crawl_delay_ms());
+    scrape_config.host_burst = scrape_config.host_burst.min(config::scrape_max_host_burst());
+
+    let max_retry_secs = config::scrape_max_retry_secs();
+    scrape_config.retry.max_attempts = scrape_config
+        .retry
+        .max_attempts
+        .min(config::scrape_max_attempts());
+    scrape_config.retry.max_elapsed_secs = scrape_config.retry.max_elapsed_secs.min(max_retry_secs);
+    scrape_config.retry.max_interval_ms = scrape_config
+        .retry
+        .max_interval_ms
+        .min(max_retry_secs * 1000);
+    scrape_config.max_retry_after_secs = scrape_config.max_retry_after_secs.min(max_retry_secs);
+    Ok(())
 }
 
 #[derive(Serialize)]
@@ -34,12 +147,28 @@ pub struct JobPollResponse {
     pub status: String,
     pub summary: Option<String>,
     pub error: Option<String>,
+    pub report: Option<CrawlReport>,
 }
 
 pub async fn scrape_url(
     Extension(state): Extension<AppState>,
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
     Json(payload): Json<ScrapeUrlPayload>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let mut scrape_config = payload.config;
+    payload.scope.apply(&mut scrape_config.scope);
+
+    // Geçersiz ayarlar (desenler, başlıklar, sıfır limitler) iş başlamadan reddedilir
+    restrict_config(&mut scrape_config)
+        .and_then(|_| scrape_config.validate())
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "message": e })),
+            )
+        })?;
+
     // 1) Yeni job_id üret, Pending olarak kaydet
     let job_id = Uuid::new_v4();
     state.jobs.insert(job_id, JobStatus::Pending);
@@ -47,73 +176,91 @@ pub async fn scrape_url(
     // 2) Arka plana işi spawn et
     let state_clone = state.clone();
     let url = payload.url.clone();
+    // İşin bütün logları (Scraper ve AIAnalyzer dahil) job_id'li bu span altında toplanır
+    let span = tracing::info_span!("scrape_job", %job_id, url = %url, user_id = %user.id);
     tokio::spawn(async move {
-        // A) spawn_blocking ile scraping
-        let pages = match tokio::task::spawn_blocking(move || {
-            // Bu closure tamamen sync kod
-            //  - Scraper::new
-            //  - scraper.scrape block_on
-            let mut scraper = Scraper::new(&url, 100, 5, 10_000)
-                .map_err(|e| format!("Init error: {}", e))?;
-            // block_on ile async scrape çalıştır
-            futures::executor::block_on(scraper.scrape(&url))
-                .map_err(|e| format!("Scrape error: {}", e))
-        })
-        .await
-        {
-            Ok(Ok(p)) if !p.is_empty() => p,
-            Ok(Ok(_)) => {
-                state_clone.jobs.insert(job_id, JobStatus::Failed {
-                    error: "No pages scraped".into(),
-                });
-                return;
-            }
-            Ok(Err(e)) => {
-                state_clone.jobs.insert(job_id, JobStatus::Failed {
-                    error: e,
-                });
-                return;
-            }
-            Err(e) => {
-                state_clone.jobs.insert(job_id, JobStatus::Failed {
-                    error: format!("Thread join error: {}", e),
-                });
-                return;
+        tracing::info!("scrape job started");
+        let mut report: Option<Box<CrawlReport>> = None;
+        let outcome: Result<String, String> = async {
+            // A) spawn_blocking ile scraping
+            let scrape_url = url.clone();
+            let span = tracing::Span::current();
+            let crawl = match tokio::task::spawn_blocking(move || {
+                // Bu closure tamamen sync kod
+                //  - Scraper::new
+                //  - scraper.scrape block_on
+                // Engelleyen iş parçacığı span'i kendiliğinden devralmaz
+                let _entered = span.enter();
+                let scraper = Scraper::new(&scrape_url, scrape_config)
+                    .map_err(|e| format!("Init error: {}", e))?;
+                // block_on ile async scrape çalıştır
+                futures::executor::block_on(scraper.scrape(&scrape_url))
+                    .map_err(|e| format!("Scrape error: {}", e))
+            })
+            .await
+            {
+                Ok(Ok(r)) => r,
+                Ok(Err(e)) => return Err(e),
+                Err(e) => return Err(format!("Thread join error: {}", e)),
+            };
+            // Sayfa toplanamasa da rapor saklanır; nedenini adres bazında gösterir
+            let crawl = report.insert(Box::new(crawl));
+            if crawl.pages.is_empty() {
+                return Err("No pages scraped".to_string());
             }
-        };
 
-        // B) Async AI analizi
-        let api_key = match std::env::var("OPENAI_API_KEY") {
-            Ok(k) => k,
-            Err(_) => {
-                state_clone.jobs.insert(job_id, JobStatus::Failed {
-                    error: "Missing OPENAI_API_KEY".into(),
-                });
-                return;
+            // B) Async AI analizi
+            let api_key = std::env::var("OPENAI_API_KEY")
+                .map_err(|_| "Missing OPENAI_API_KEY".to_string())?;
+            AIAnalyzer::new(api_key)
+                .analyze_pages(&crawl.pages)
+                .await
+                .map_err(|e| format!("AI error: {}", e))
+        }
+        .await;
+
+        // C) Sonucu kaydet ve işi başlatan kullanıcıya bildir
+        let (kind, data) = match outcome {
+            Ok(summary) => {
+                tracing::info!("scrape job finished");
+                let report = report.expect("report is set before analysis");
+                state_clone.finish(job_id, JobStatus::Done { summary, report });
+                (NotificationKind::ScrapeDone, json!({ "job_id": job_id, "url": url }))
             }
-        };
-        let summary = match AIAnalyzer::new(api_key).analyze_pages(&pages).await {
-            Ok(text) => text,
-            Err(e) => {
-                state_clone.jobs.insert(job_id, JobStatus::Failed {
-                    error: format!("AI error: {}", e),
-                });
-                return;
+            Err(error) => {
+                tracing::warn!(%error, "scrape job failed");
+                state_clone.finish(job_id, JobStatus::Failed { error: error.clone(), report });
+                (
+                    NotificationKind::ScrapeFailed,
+                    json!({ "job_id": job_id, "url": url, "error": error }),
+                )
             }
         };
-
-        // C) Başarı durumu
-        state_clone.jobs.insert(job_id, JobStatus::Done { summary });
-    });
+        notify(
+            &pool,
+            NewNotification {
+                user_id: &user.id,
+                kind,
+                actor_id: None,
+                workspace_id: None,
+                page_id: None,
+                data,
+            },
+        )
+        .await;
+    }
+    .instrument(span));
 
     // 3) Hemen 202 ve job_id dön
-    (StatusCode::ACCEPTED, Json(json!(JobCreated { job_id })))
+    Ok((StatusCode::ACCEPTED, Json(json!(JobCreated { job_id }))))
 }
 /// GET /api/jobs/:id
 pub async fn poll_job(
     Extension(state): Extension<AppState>,
     Path(id): Path<Uuid>,
 ) -> impl IntoResponse {
+    // Süresi dolan sonuçlar, yeni iş bitmese de sorgulanırken temizlenir
+    state.evict_finished();
     if let Some(status) = state.jobs.get(&id) {
         // 4) Status'a göre yanıtı oluştur
         let resp = match status.value() {
@@ -121,16 +268,19 @@ pub async fn poll_job(
                 status: "pending".to_string(),
                 summary: None,
                 error: None,
+                report: None,
             },
-            JobStatus::Done { summary } => JobPollResponse {
+            JobStatus::Done { summary, report } => JobPollResponse {
                 status: "done".to_string(),
                 summary: Some(summary.clone()),
                 error: None,
+                report: Some(report.as_ref().clone()),
             },
-            JobStatus::Failed { error } => JobPollResponse {
+            JobStatus::Failed { error, report } => JobPollResponse {
                 status: "failed".to_string(),
                 summary: None,
                 error: Some(error.clone()),
+                report: report.as_deref().cloned(),
             },
         };
         (StatusCode::OK, Json(json!(resp)))
@@ -0,0 +1,503 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{MySqlConnection, MySqlPool};
+use uuid::Uuid;
+
+use crate::content::blocks;
+use crate::content::links::sync_references;
+use crate::content::search::plain_text;
+use crate::content::template::{render_str, render_value, TemplatePage, TemplateVars};
+use crate::handlers::access::{page_workspace_id, require_editor, workspace_role};
+use crate::handlers::page_tree::check_parent;
+use crate::models::user::User;
+use crate::webhooks::{dispatch, WebhookEvent};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageTemplate {
+    pub id: String,
+    pub workspace_id: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub title: String,
+    pub content: Option<sqlx::types::Json<Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTemplatePayload {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Boşsa şablon tüm workspace'lerde kullanılabilir (sadece admin)
+    #[serde(default)]
+    pub workspace_id: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub content: Option<Value>,
+    /// Verilirse başlık ve içerik bu sayfadan kopyalanır
+    #[serde(default)]
+    pub from_page_id: Option<String>,
+}
+
+pub async fn create_template(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CreateTemplatePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    match &payload.workspace_id {
+        Some(workspace_id) => {
+            require_editor(&pool, workspace_id, &user.id, "Viewers cannot create templates").await?;
+        }
+        None => require_admin(&user)?,
+    }
+
+    let (title, content) = match &payload.from_page_id {
+        Some(page_id) => {
+            let workspace_id = page_workspace_id(&pool, page_id).await?;
+            if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(json!({ "message": "You don't have access to this workspace"})),
+                ));
+            }
+            let page = sqlx::query!(
+                r#"SELECT title, content as "content: sqlx::types::Json<Value>" FROM pages WHERE id = ?"#,
+                page_id
+            )
+            .fetch_one(&pool)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+            (payload.title.clone().unwrap_or(page.title), page.content.map(|c| c.0))
+        }
+        None => (
+            payload.title.clone().unwrap_or_else(|| payload.name.clone()),
+            payload.content.clone(),
+        ),
+    };
+
+    if let Some(content) = &content {
+        blocks::validate(content).map_err(|errors| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(json!({ "message": "Invalid page content", "errors": errors })),
+            )
+        })?;
+    }
+
+    let template_id = Uuid::new_v4().to_string();
+    sqlx::query!(
+        "INSERT INTO page_templates (id, workspace_id, name, description, title, content, created_by)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        template_id,
+        payload.workspace_id,
+        payload.name,
+        payload.description,
+        title,
+        content.map(sqlx::types::Json),
+        user.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Template created successfully", "template_id": template_id })),
+    ))
+}
+
+/// GET /api/workspaces/:id/templates — genel şablonlar ve workspace'e ait olanlar
+pub async fn get_templates(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(workspace_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Workspace not found or you don't have access" })),
+        ));
+    }
+
+    let templates = sqlx::query_as!(
+        PageTemplate,
+        r#"
+        SELECT id, workspace_id, name, description, title,
+               content as "content: sqlx::types::Json<Value>"
+        FROM page_templates
+        WHERE workspace_id IS NULL OR workspace_id = ?
+        ORDER BY name
+        "#,
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(templates)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteTemplatePayload {
+    pub id: String,
+}
+
+pub async fn delete_template(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<DeleteTemplatePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let template = fetch_template(&pool, &payload.id).await?;
+    match &template.workspace_id {
+        Some(workspace_id) => {
+            require_editor(&pool, workspace_id, &user.id, "Viewers cannot delete templates").await?;
+        }
+        None => require_admin(&user)?,
+    }
+
+    sqlx::query!("DELETE FROM page_templates WHERE id = ?", payload.id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Template deleted successfully" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatePageFromTemplatePayload {
+    pub template_id: String,
+    pub workspace_id: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Verilmezse şablonun başlığı kullanılır
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+pub async fn create_page_from_template(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CreatePageFromTemplatePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_editor(&pool, &payload.workspace_id, &user.id, "Viewers cannot create pages").await?;
+
+    let template = fetch_template(&pool, &payload.template_id).await?;
+    if template.workspace_id.as_ref().is_some_and(|w| *w != payload.workspace_id) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Template not found"})),
+        ));
+    }
+
+    if let Some(parent_id) = &payload.parent_id {
+        check_parent(&pool, parent_id, &payload.workspace_id).await?;
+    }
+
+    let mut vars = template_vars(&user, &template.name);
+    let title = match &payload.title {
+        Some(title) => title.clone(),
+        None => render_str(&template.title, &vars),
+    };
+    vars.title = title.clone();
+
+    let content = template.content.map(|content| {
+        let mut content = content.0;
+        render_value(&mut content, &vars);
+        content
+    });
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    let page_id = Uuid::new_v4().to_string();
+    sqlx::query!(
+        "INSERT INTO pages (id, title, workspace_id, parent_id, content, content_text, created_by) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        page_id,
+        title,
+        payload.workspace_id,
+        payload.parent_id,
+        content.as_ref().map(sqlx::types::Json),
+        content.as_ref().map(plain_text),
+        user.id
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?;
+
+    if let Some(content) = &content {
+        sync_references(&mut tx, &page_id, content, &user.id)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "message": "Database error" })),
+                )
+            })?;
+    }
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    dispatch(
+        &pool,
+        &payload.workspace_id,
+        WebhookEvent::PageCreated,
+        json!({ "page_id": page_id, "title": title, "parent_id": payload.parent_id, "template_id": payload.template_id, "actor_id": user.id }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Page created successfully",
+        "page_id": page_id
+         })),
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkspaceTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub pages: sqlx::types::Json<Vec<TemplatePage>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWorkspaceTemplatePayload {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub pages: Vec<TemplatePage>,
+}
+
+pub async fn create_workspace_template(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CreateWorkspaceTemplatePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_admin(&user)?;
+
+    // Ağaçtaki her sayfanın içeriği blok modeline uymalı
+    let mut stack: Vec<&TemplatePage> = payload.pages.iter().collect();
+    while let Some(page) = stack.pop() {
+        if let Some(content) = &page.content {
+            blocks::validate(content).map_err(|errors| {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "message": "Invalid page content",
+                        "page": page.title,
+                        "errors": errors,
+                    })),
+                )
+            })?;
+        }
+        stack.extend(page.children.iter());
+    }
+
+    let template_id = Uuid::new_v4().to_string();
+    sqlx::query!(
+        "INSERT INTO workspace_templates (id, name, description, pages, created_by) VALUES (?, ?, ?, ?, ?)",
+        template_id,
+        payload.name,
+        payload.description,
+        sqlx::types::Json(&payload.pages),
+        user.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({ "message": "Template created successfully", "template_id": template_id })),
+    ))
+}
+
+pub async fn get_workspace_templates(
+    Extension(pool): Extension<MySqlPool>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let templates = sqlx::query_as!(
+        WorkspaceTemplate,
+        r#"
+        SELECT id, name, description, pages as "pages: sqlx::types::Json<Vec<TemplatePage>>"
+        FROM workspace_templates
+        ORDER BY name
+        "#
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(templates)))
+}
+
+/// Workspace şablonundaki sayfa ağacını yeni workspace'te oluşturur.
+pub async fn instantiate_workspace_template(
+    conn: &mut MySqlConnection,
+    template_id: &str,
+    workspace_id: &str,
+    user: &User,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let template = sqlx::query!(
+        r#"SELECT name, pages as "pages: sqlx::types::Json<Vec<TemplatePage>>" FROM workspace_templates WHERE id = ?"#,
+        template_id
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Workspace template not found"})),
+        )
+    })?;
+
+    let vars = template_vars(user, &template.name);
+    let mut stack: Vec<(Option<String>, TemplatePage)> =
+        template.pages.0.into_iter().rev().map(|p| (None, p)).collect();
+
+    while let Some((parent_id, page)) = stack.pop() {
+        let page_id = Uuid::new_v4().to_string();
+        let title = render_str(&page.title, &vars);
+        let page_vars = TemplateVars { title: title.clone(), ..vars.clone() };
+        let content = page.content.map(|mut content| {
+            render_value(&mut content, &page_vars);
+            content
+        });
+
+        sqlx::query!(
+            "INSERT INTO pages (id, title, workspace_id, parent_id, content, content_text, created_by) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            page_id,
+            title,
+            workspace_id,
+            parent_id,
+            content.as_ref().map(sqlx::types::Json),
+            content.as_ref().map(plain_text),
+            user.id
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+        if let Some(content) = &content {
+            sync_references(&mut *conn, &page_id, content, &user.id)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "message": "Database error" })),
+                    )
+                })?;
+        }
+
+        for child in page.children.into_iter().rev() {
+            stack.push((Some(page_id.clone()), child));
+        }
+    }
+
+    Ok(())
+}
+
+fn template_vars(user: &User, title: &str) -> TemplateVars {
+    TemplateVars {
+        date: Utc::now().format("%Y-%m-%d").to_string(),
+        author: user.username.clone(),
+        title: title.to_string(),
+    }
+}
+
+fn require_admin(user: &User) -> Result<(), (StatusCode, Json<Value>)> {
+    if user.role == "admin" {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({ "message": "Only admins can manage global templates"})),
+        ))
+    }
+}
+
+async fn fetch_template(
+    pool: &MySqlPool,
+    template_id: &str,
+) -> Result<PageTemplate, (StatusCode, Json<Value>)> {
+    sqlx::query_as!(
+        PageTemplate,
+        r#"
+        SELECT id, workspace_id, name, description, title,
+               content as "content: sqlx::types::Json<Value>"
+        FROM page_templates WHERE id = ?
+        "#,
+        template_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Template not found"})),
+        )
+    })
+}
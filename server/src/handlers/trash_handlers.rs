@@ -0,0 +1,290 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+
+use crate::audit::{record, AuditEvent, ClientIp};
+use crate::config;
+use crate::handlers::access::{require_editor, workspace_role};
+use crate::handlers::page_tree::subtree_ids;
+use crate::models::user::User;
+use crate::storage::SharedStorage;
+use crate::webhooks::{dispatch, WebhookEvent};
+use crate::workers::trash_purge::{delete_subtree, purge_orphaned_attachments};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedPage {
+    pub id: String,
+    pub title: String,
+    pub workspace_id: String,
+    pub parent_id: Option<String>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub deleted_by: Option<String>,
+    pub deleted_by_username: Option<String>,
+    pub purge_at: Option<DateTime<Utc>>,
+}
+
+pub async fn get_trash(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(workspace_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    if workspace_role(&pool, &workspace_id, &user.id).await?.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Workspace not found or you don't have access" })),
+        ));
+    }
+
+    let pages = sqlx::query_as!(
+        TrashedPage,
+        r#"
+        SELECT p.id, p.title, p.workspace_id, p.parent_id, p.deleted_at, p.deleted_by,
+               u.username as "deleted_by_username?",
+               p.deleted_at + INTERVAL ? DAY as "purge_at?: DateTime<Utc>"
+        FROM pages p
+        LEFT JOIN users u ON p.deleted_by = u.id
+        WHERE p.workspace_id = ? AND p.deleted_at IS NOT NULL
+        ORDER BY p.deleted_at DESC
+        "#,
+        config::trash_retention_days(),
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(pages)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashPagePayload {
+    pub id: String,
+}
+
+pub async fn restore_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<TrashPagePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let page = sqlx::query!(
+        "SELECT workspace_id, title, parent_id, deleted_at FROM pages WHERE id = ? AND deleted_at IS NOT NULL",
+        payload.id
+    )
+    .fetch_optional(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?
+    .ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Page not found in trash"})),
+        )
+    })?;
+
+    require_editor(&pool, &page.workspace_id, &user.id, "Viewers cannot restore pages").await?;
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    // Sadece sayfayla aynı anda silinen alt sayfaları geri getir
+    let page_ids = subtree_ids(&mut tx, &payload.id, true).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    for page_id in &page_ids {
+        sqlx::query!(
+            "UPDATE pages SET deleted_at = NULL, deleted_by = NULL WHERE id = ? AND deleted_at = ?",
+            page_id,
+            page.deleted_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+    }
+
+    // Ebeveyn hâlâ çöpteyse sayfa kök seviyesine alınır
+    let mut parent_id = page.parent_id.clone();
+    if let Some(trashed_parent_id) = &page.parent_id {
+        let parent_trashed = sqlx::query!(
+            "SELECT id FROM pages WHERE id = ? AND deleted_at IS NOT NULL",
+            trashed_parent_id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?
+        .is_some();
+
+        if parent_trashed {
+            sqlx::query!("UPDATE pages SET parent_id = NULL WHERE id = ?", payload.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "message": "Database error" })),
+                    )
+                })?;
+            parent_id = None;
+        }
+    }
+
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&page.workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.restored",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: Some(json!({ "title": page.title, "deleted_at": page.deleted_at })),
+            after: Some(json!({ "parent_id": parent_id, "restored_page_ids": page_ids })),
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+    // Abonelere geri gelen sayfa yeniden oluşturulmuş gibi bildirilir
+    dispatch(
+        &pool,
+        &page.workspace_id,
+        WebhookEvent::PageCreated,
+        json!({
+            "page_id": payload.id,
+            "title": page.title,
+            "parent_id": parent_id,
+            "restored": true,
+            "restored_page_ids": page_ids,
+            "actor_id": user.id,
+        }),
+    )
+    .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "message": "Page restored successfully",
+            "pages_restored": page_ids.len(),
+        })),
+    ))
+}
+
+pub async fn purge_page(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(storage): Extension<SharedStorage>,
+    Extension(user): Extension<User>,
+    ClientIp(ip): ClientIp,
+    Json(payload): Json<TrashPagePayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let page = trashed_page(&pool, &payload.id).await?;
+    require_editor(&pool, &page.workspace_id, &user.id, "Viewers cannot delete pages").await?;
+
+    let mut tx = pool.begin().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+    let page_ids = delete_subtree(&mut tx, &payload.id).await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+    tx.commit().await.map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    record(
+        &pool,
+        AuditEvent {
+            workspace_id: Some(&page.workspace_id),
+            actor_id: Some(&user.id),
+            action: "page.purged",
+            target_type: "page",
+            target_id: Some(&payload.id),
+            before: Some(json!({ "title": page.title, "purged_page_ids": page_ids })),
+            after: None,
+            ip: ip.as_deref(),
+        },
+    )
+    .await;
+
+    // Alt sayfalar da silindiği için bunların eklentileri de temizlenir; hata olursa
+    // çöp temizleyici sonra yeniden dener
+    if let Err(e) = purge_orphaned_attachments(&pool, &storage).await {
+        tracing::warn!(error = %e, "attachment cleanup failed");
+    }
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Page deleted permanently" }))))
+}
+
+struct TrashedPageRef {
+    workspace_id: String,
+    title: String,
+}
+
+/// Çöp kutusundaki sayfanın workspace'ini ve başlığını döndürür; sayfa çöpte değilse 404.
+async fn trashed_page(
+    pool: &MySqlPool,
+    page_id: &str,
+) -> Result<TrashedPageRef, (StatusCode, Json<Value>)> {
+    let page = sqlx::query_as!(
+        TrashedPageRef,
+        "SELECT workspace_id, title FROM pages WHERE id = ? AND deleted_at IS NOT NULL",
+        page_id
+    )
+    .fetch_optional(pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error"})),
+        )
+    })?;
+
+    match page {
+        Some(p) => Ok(p),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Page not found in trash"})),
+        )),
+    }
+}
@@ -0,0 +1,324 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::handlers::access::require_owner;
+use crate::models::user::User;
+use crate::webhooks::{check_destination, enqueue, WebhookEvent};
+
+const MANAGE_WEBHOOKS_MESSAGE: &str = "Only the workspace owner can manage webhooks";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub workspace_id: String,
+    pub url: String,
+    pub events: sqlx::types::Json<Vec<String>>,
+    pub active: bool,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhookPayload {
+    pub workspace_id: String,
+    pub url: String,
+    /// Boş bırakılırsa bütün olaylar gönderilir
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Verilmezse rastgele üretilir; sadece bu yanıtta döner
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+pub async fn create_webhook(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<CreateWebhookPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_owner(&pool, &payload.workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+    check_url(&payload.url).await?;
+    check_events(&payload.events)?;
+
+    let id = Uuid::new_v4().to_string();
+    let secret = payload
+        .secret
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()));
+
+    sqlx::query!(
+        "INSERT INTO webhooks (id, workspace_id, url, secret, events, created_by) VALUES (?, ?, ?, ?, ?, ?)",
+        id,
+        payload.workspace_id,
+        payload.url,
+        secret,
+        sqlx::types::Json(&payload.events),
+        user.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "message": "Webhook created successfully",
+            "webhookId": id,
+            "secret": secret,
+        })),
+    ))
+}
+
+/// GET /api/workspaces/:id/webhooks — secret'lar listelenmez
+pub async fn get_webhooks(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(workspace_id): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    require_owner(&pool, &workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+
+    let webhooks = sqlx::query_as!(
+        Webhook,
+        r#"
+        SELECT id, workspace_id, url, events as "events: sqlx::types::Json<Vec<String>>",
+               active as "active: bool", created_at
+        FROM webhooks
+        WHERE workspace_id = ?
+        ORDER BY created_at
+        "#,
+        workspace_id
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(webhooks)))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateWebhookPayload {
+    pub id: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub events: Option<Vec<String>>,
+    #[serde(default)]
+    pub active: Option<bool>,
+}
+
+pub async fn update_webhook(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<UpdateWebhookPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = webhook_workspace_id(&pool, &payload.id).await?;
+    require_owner(&pool, &workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+    if let Some(url) = &payload.url {
+        check_url(url).await?;
+    }
+    if let Some(events) = &payload.events {
+        check_events(events)?;
+    }
+
+    sqlx::query!(
+        "
+        UPDATE webhooks
+        SET url = COALESCE(?, url), events = COALESCE(?, events), active = COALESCE(?, active)
+        WHERE id = ?
+        ",
+        payload.url,
+        payload.events.as_ref().map(sqlx::types::Json),
+        payload.active,
+        payload.id
+    )
+    .execute(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Webhook updated successfully" }))))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookIdPayload {
+    pub id: String,
+}
+
+pub async fn delete_webhook(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<WebhookIdPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = webhook_workspace_id(&pool, &payload.id).await?;
+    require_owner(&pool, &workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+
+    sqlx::query!("DELETE FROM webhooks WHERE id = ?", payload.id)
+        .execute(&pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    Ok((StatusCode::OK, Json(json!({ "message": "Webhook deleted successfully" }))))
+}
+
+/// Filtreden bağımsız bir `ping` teslimatı kuyruğa ekler; alıcıyı denemek için.
+pub async fn test_webhook(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<WebhookIdPayload>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = webhook_workspace_id(&pool, &payload.id).await?;
+    require_owner(&pool, &workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+
+    let delivery_id = enqueue(
+        &pool,
+        &payload.id,
+        &workspace_id,
+        WebhookEvent::Ping,
+        &json!({ "triggered_by": user.id }),
+    )
+    .await
+    .ok_or_else(|| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({ "message": "Ping queued", "deliveryId": delivery_id })),
+    ))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub event: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_status_code: Option<u16>,
+    pub last_error: Option<String>,
+    pub payload: sqlx::types::Json<Value>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryListParams {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// GET /api/webhooks/:id/deliveries?status=failed&limit=50
+pub async fn get_webhook_deliveries(
+    Extension(pool): Extension<MySqlPool>,
+    Extension(user): Extension<User>,
+    Path(webhook_id): Path<String>,
+    Query(params): Query<DeliveryListParams>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    let workspace_id = webhook_workspace_id(&pool, &webhook_id).await?;
+    require_owner(&pool, &workspace_id, &user.id, MANAGE_WEBHOOKS_MESSAGE).await?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+
+    let deliveries = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        SELECT id, event, status, attempts, last_status_code as "last_status_code: u16", last_error,
+               payload as "payload: sqlx::types::Json<Value>",
+               next_attempt_at, delivered_at, created_at
+        FROM webhook_deliveries
+        WHERE webhook_id = ? AND (? IS NULL OR status = ?)
+        ORDER BY created_at DESC
+        LIMIT ?
+        "#,
+        webhook_id,
+        params.status,
+        params.status,
+        limit
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|_| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Database error" })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, Json(deliveries)))
+}
+
+async fn webhook_workspace_id(
+    pool: &MySqlPool,
+    webhook_id: &str,
+) -> Result<String, (StatusCode, Json<Value>)> {
+    let webhook = sqlx::query!("SELECT workspace_id FROM webhooks WHERE id = ?", webhook_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Database error" })),
+            )
+        })?;
+
+    match webhook {
+        Some(w) => Ok(w.workspace_id),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "message": "Webhook not found" })),
+        )),
+    }
+}
+
+async fn check_url(url: &str) -> Result<(), (StatusCode, Json<Value>)> {
+    check_destination(url)
+        .await
+        .map(|_| ())
+        .map_err(|message| (StatusCode::BAD_REQUEST, Json(json!({ "message": message }))))
+}
+
+fn check_events(events: &[String]) -> Result<(), (StatusCode, Json<Value>)> {
+    let unknown: Vec<&String> = events
+        .iter()
+        .filter(|e| WebhookEvent::parse(e).is_none())
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    let known: Vec<&str> = WebhookEvent::ALL.iter().map(|e| e.as_str()).collect();
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "message": "Unknown webhook events", "unknown": unknown, "known": known })),
+    ))
+}
@@ -1,10 +1,19 @@
 mod routes;
+mod audit;
+mod avatar;
+mod content;
+mod config;
 mod db;
 mod handlers;
 mod models;
+mod notifications;
 use std::env;
+use std::net::SocketAddr;
 mod middleware;
 mod state;
+mod storage;
+mod webhooks;
+mod workers;
 
 use axum::{
     http::{header, HeaderValue, Method}, middleware::from_fn_with_state, routing::get, Extension, Router
@@ -13,20 +22,61 @@ use state::AppState;
 use tokio::net::TcpListener;
 use tower_cookies::CookieManagerLayer;
 use tower_http::cors::{ CorsLayer};
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::EnvFilter;
 
-use routes::{auth::auth_routes, body::{body_routes}};
+use routes::{auth::auth_routes, body::{body_routes}, public::public_routes};
 use handlers::auth_handlers::me;
 use middleware::auth_middleware::auth_middleware;
 
 
+fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    if config::log_json() {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+}
+
 #[tokio::main]
 async fn main(){
 
     dotenvy::dotenv().ok();
+
+    // `server check-content`: blok modeline uymayan sayfaları raporla ve çık
+    if env::args().nth(1).as_deref() == Some("check-content") {
+        let pool = db::init_db().await.unwrap();
+        let invalid = content::report::invalid_pages(&pool).await.unwrap();
+        for page in &invalid {
+            println!("{}", serde_json::to_string(page).unwrap());
+        }
+        eprintln!("{} page(s) failed validation", invalid.len());
+        return;
+    }
+
+    // `server reindex-search`: tüm sayfaların arama metnini yeniden üret
+    if env::args().nth(1).as_deref() == Some("reindex-search") {
+        let pool = db::init_db().await.unwrap();
+        let count = content::search::reindex_all(&pool).await.unwrap();
+        eprintln!("{} page(s) reindexed", count);
+        return;
+    }
+
+    init_tracing();
+
     let client_url = env::var("CLIENT_URL").expect("CLIENT_URL must be set");
     
     let pool = db::init_db().await.unwrap();
 
+    let storage = storage::from_env();
+
+    workers::trash_purge::spawn(pool.clone(), storage.clone(), config::trash_retention_days());
+    workers::webhook_delivery::spawn(pool.clone());
+
     let app_state = AppState::new(); 
 
     let cors = CorsLayer::new()
@@ -45,10 +95,14 @@ async fn main(){
     .nest("/auth", auth_routes(pool.clone()))
     .nest("/api", protected,)
     .nest("/api", body_routes(pool.clone()))
+    .nest("/api", public_routes())
      .layer(CookieManagerLayer::new())
     .layer(Extension(pool.clone()))
     .layer(Extension(app_state.clone()))
-    .layer(cors);
+    .layer(Extension(storage))
+    .layer(cors)
+    // Her istek için method/uri span'i; handler'lardaki loglar bu span altında görünür
+    .layer(TraceLayer::new_for_http());
 
 
 
@@ -56,7 +110,11 @@ async fn main(){
 
 
     let listener = TcpListener::bind("127.0.0.1:3000").await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    tracing::info!(addr = %listener.local_addr().unwrap(), "listening");
+    // ClientIp, bağlantı adresini ConnectInfo üzerinden okur
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
 
 
@@ -0,0 +1,67 @@
+use serde_json::Value;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy)]
+pub enum NotificationKind {
+    WorkspaceInvite,
+    MemberRemoved,
+    RoleChanged,
+    Mention,
+    PageRenamed,
+    PageDeleted,
+    ScrapeDone,
+    ScrapeFailed,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::WorkspaceInvite => "workspace_invite",
+            NotificationKind::MemberRemoved => "member_removed",
+            NotificationKind::RoleChanged => "role_changed",
+            NotificationKind::Mention => "mention",
+            NotificationKind::PageRenamed => "page_renamed",
+            NotificationKind::PageDeleted => "page_deleted",
+            NotificationKind::ScrapeDone => "scrape_done",
+            NotificationKind::ScrapeFailed => "scrape_failed",
+        }
+    }
+}
+
+pub struct NewNotification<'a> {
+    pub user_id: &'a str,
+    pub kind: NotificationKind,
+    pub actor_id: Option<&'a str>,
+    pub workspace_id: Option<&'a str>,
+    pub page_id: Option<&'a str>,
+    pub data: Value,
+}
+
+/// Bildirimi kaydeder. Bildirimler asıl işlemin parçası değildir; hata olursa
+/// sadece loglanır ve çağıran işlem etkilenmez.
+pub async fn notify(pool: &MySqlPool, notification: NewNotification<'_>) {
+    let id = Uuid::new_v4().to_string();
+    let result = sqlx::query!(
+        "INSERT INTO notifications (id, user_id, kind, actor_id, workspace_id, page_id, data)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        id,
+        notification.user_id,
+        notification.kind.as_str(),
+        notification.actor_id,
+        notification.workspace_id,
+        notification.page_id,
+        sqlx::types::Json(&notification.data)
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!(
+            kind = notification.kind.as_str(),
+            user_id = %notification.user_id,
+            error = %e,
+            "failed to store notification"
+        );
+    }
+}
@@ -1,9 +1,25 @@
+use axum::extract::DefaultBodyLimit;
 use axum::routing::get;
 use axum::{middleware::from_fn_with_state, routing::post, Router};
 use sqlx::MySqlPool;
+use crate::config;
 use crate::middleware::auth_middleware::auth_middleware;
-use crate::handlers::body_handlers::{create_page, create_workspace, delete_page, get_page, get_workspace_id, get_workspace_members, get_workspace_pages, get_workspaces, rename_page, update_page};
+use crate::handlers::attachment_handlers::{delete_attachment, download_attachment, get_page_attachments, upload_attachment};
+use crate::handlers::audit_handlers::{get_audit_log, get_workspace_activity};
+use crate::handlers::avatar_handlers::upload_avatar;
+use crate::handlers::body_handlers::{create_page, create_workspace, delete_page, get_page, get_workspace_id, get_workspace_members, get_workspace_pages, get_workspaces, patch_page, rename_page, update_page};
+use crate::handlers::comment_handlers::{create_comment, delete_comment, edit_comment, get_comments, reopen_comment, resolve_comment, update_comment_settings};
+use crate::handlers::export_handlers::{export_page, export_workspace};
+use crate::handlers::import_handlers::import_pages;
+use crate::handlers::link_handlers::{get_backlinks, get_my_mentions, get_page_links};
+use crate::handlers::member_handlers::{add_member, remove_member, update_member_role};
+use crate::handlers::notification_handlers::{get_notifications, mark_all_notifications_read, mark_notifications_read};
+use crate::handlers::page_tree::{duplicate_page, move_page};
+use crate::handlers::search_handlers::search_pages;
 use crate::handlers::service_handler::{poll_job, scrape_url};
+use crate::handlers::template_handlers::{create_page_from_template, create_template, create_workspace_template, delete_template, get_templates, get_workspace_templates};
+use crate::handlers::trash_handlers::{get_trash, purge_page, restore_page};
+use crate::handlers::webhook_handlers::{create_webhook, delete_webhook, get_webhook_deliveries, get_webhooks, test_webhook, update_webhook};
 pub fn body_routes(pool:MySqlPool) -> Router{
     Router::new()
     .route("/createworkspace", post(create_workspace))
@@ -11,11 +27,57 @@ pub fn body_routes(pool:MySqlPool) -> Router{
     .route("/workspaces/{workspaceId}", get(get_workspace_id))
     .route("/workspaces/{workspaceId}/pages", get(get_workspace_pages))
     .route("/workspaces/{workspaceId}/members", get(get_workspace_members))
+    .route("/upload-avatar", post(upload_avatar).layer(DefaultBodyLimit::max(config::avatar_max_bytes() + 64 * 1024)))
+    .route("/workspaces/{workspaceId}/activity", get(get_workspace_activity))
+    .route("/admin/audit", get(get_audit_log))
+    .route("/add-member",post(add_member))
+    .route("/remove-member",post(remove_member))
+    .route("/update-member-role",post(update_member_role))
     .route("/create-page", post(create_page))
     .route("/get-page/{pageId}", get(get_page))
+    .route("/pages/{pageId}/export", get(export_page))
+    .route("/workspaces/{workspaceId}/export", get(export_workspace))
+    .route("/import", post(import_pages).layer(DefaultBodyLimit::max(config::import_max_bytes())))
+    // Dosya sınırı handler'da uygulanır; gövde sınırı multipart başlıkları için biraz daha geniş
+    .route("/upload-attachment", post(upload_attachment).layer(DefaultBodyLimit::max(config::upload_max_bytes() + 64 * 1024)))
+    .route("/attachments/{attachmentId}", get(download_attachment))
+    .route("/pages/{pageId}/attachments", get(get_page_attachments))
+    .route("/delete-attachment", post(delete_attachment))
     .route("/rename-page",post(rename_page))
     .route("/delete-page",post(delete_page))
     .route("/update-page",post(update_page))
+    .route("/patch-page",post(patch_page))
+    .route("/duplicate-page",post(duplicate_page))
+    .route("/move-page",post(move_page))
+    .route("/pages/{pageId}/backlinks", get(get_backlinks))
+    .route("/pages/{pageId}/links", get(get_page_links))
+    .route("/mentions", get(get_my_mentions))
+    .route("/pages/{pageId}/comments", get(get_comments))
+    .route("/create-comment",post(create_comment))
+    .route("/edit-comment",post(edit_comment))
+    .route("/delete-comment",post(delete_comment))
+    .route("/resolve-comment",post(resolve_comment))
+    .route("/reopen-comment",post(reopen_comment))
+    .route("/comment-settings",post(update_comment_settings))
+    .route("/workspaces/{workspaceId}/trash", get(get_trash))
+    .route("/restore-page",post(restore_page))
+    .route("/purge-page",post(purge_page))
+    .route("/workspaces/{workspaceId}/templates", get(get_templates))
+    .route("/create-template",post(create_template))
+    .route("/delete-template",post(delete_template))
+    .route("/create-page-from-template",post(create_page_from_template))
+    .route("/workspace-templates", get(get_workspace_templates))
+    .route("/create-workspace-template",post(create_workspace_template))
+    .route("/search", get(search_pages))
+    .route("/workspaces/{workspaceId}/webhooks", get(get_webhooks))
+    .route("/create-webhook",post(create_webhook))
+    .route("/update-webhook",post(update_webhook))
+    .route("/delete-webhook",post(delete_webhook))
+    .route("/test-webhook",post(test_webhook))
+    .route("/webhooks/{webhookId}/deliveries", get(get_webhook_deliveries))
+    .route("/notifications", get(get_notifications))
+    .route("/mark-notifications-read",post(mark_notifications_read))
+    .route("/mark-all-notifications-read",post(mark_all_notifications_read))
     .route("/scrape",post(scrape_url))
     .route("/jobs/{id}",get(poll_job))
     .layer(from_fn_with_state(pool.clone(), auth_middleware))
@@ -0,0 +1,8 @@
+use axum::{routing::get, Router};
+use crate::handlers::avatar_handlers::get_avatar;
+
+/// Oturum gerektirmeyen /api rotaları
+pub fn public_routes() -> Router {
+    Router::new()
+    .route("/avatars/{userId}/{version}", get(get_avatar))
+}
@@ -1,27 +1,58 @@
 // back-end/src/state.rs
 use dashmap::DashMap;
+use scraper_service_cli::report::CrawlReport;
 use serde::Serialize;
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-// Bir işin durumu:
+use crate::config;
+
+// Bir işin durumu; tarama yapıldıysa adres bazında rapor da saklanır
 #[derive(Clone, Serialize, Debug)]
 pub enum JobStatus {
     Pending,
-    Done { summary: String },
-    Failed { error: String },
+    Done { summary: String, report: Box<CrawlReport> },
+    Failed { error: String, report: Option<Box<CrawlReport>> },
 }
 
 // Uygulama durumu: job_id → JobStatus
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub jobs: Arc<DashMap<Uuid, JobStatus>>,
+    // Biten işler bitiş sırasıyla; raporları bellekte sınırsız birikmesin diye eskileri silinir
+    finished: Arc<Mutex<VecDeque<(Instant, Uuid)>>>,
 }
 
 impl AppState {
     pub fn new() -> Self {
         AppState {
             jobs: Arc::new(DashMap::new()),
+            finished: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    // İşin sonucunu kaydeder; süresi dolan ya da sayı sınırını aşan eski sonuçlar silinir
+    pub fn finish(&self, job_id: Uuid, status: JobStatus) {
+        self.jobs.insert(job_id, status);
+        self.finished
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push_back((Instant::now(), job_id));
+        self.evict_finished();
+    }
+
+    pub fn evict_finished(&self) {
+        let ttl = Duration::from_secs(config::scrape_job_ttl_secs());
+        let max = config::scrape_max_finished_jobs();
+        let mut finished = self.finished.lock().unwrap_or_else(|e| e.into_inner());
+        while let Some(&(at, job_id)) = finished.front() {
+            if finished.len() <= max && at.elapsed() < ttl {
+                break;
+            }
+            self.jobs.remove(&job_id);
+            finished.pop_front();
         }
     }
 }
@@ -0,0 +1,94 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+use uuid::Uuid;
+
+use super::{Storage, StorageError};
+
+/// Dosyaları yerel diskte, anahtarı göreli yol olarak kullanarak saklar.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStorage { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        // Anahtarlar sunucuda üretilse de kök klasörün dışına çıkılmasına izin verilmez
+        if key.is_empty() || key.starts_with('/') || key.split('/').any(|s| s.is_empty() || s == "." || s == "..") {
+            return Err(StorageError::Backend(format!("invalid storage key: {}", key)));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+fn io_error(e: std::io::Error) -> StorageError {
+    match e.kind() {
+        ErrorKind::NotFound => StorageError::NotFound,
+        _ => StorageError::Backend(e.to_string()),
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(io_error)?;
+        }
+        // Yarım kalmış bir yazma okunmasın diye önce geçici dosyaya yazılır
+        let temp = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        tokio::fs::write(&temp, &data).await.map_err(io_error)?;
+        if let Err(e) = tokio::fs::rename(&temp, &path).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(io_error(e));
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Bytes, StorageError> {
+        let path = self.path(key)?;
+        tokio::fs::read(path).await.map(Bytes::from).map_err(io_error)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_rejects_keys_outside_root() {
+        let storage = LocalStorage::new("/var/uploads");
+        for key in ["", "/etc/passwd", "../secret", "ws/../../secret", "ws/./a", "ws//a", "ws/"] {
+            assert!(storage.path(key).is_err(), "{:?} should be rejected", key);
+        }
+        assert_eq!(storage.path("ws/a").unwrap(), PathBuf::from("/var/uploads/ws/a"));
+    }
+
+    #[tokio::test]
+    async fn put_get_delete() {
+        let root = std::env::temp_dir().join(format!("local-storage-{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(&root);
+
+        storage.put("ws/a", Bytes::from_static(b"data"), "text/plain").await.unwrap();
+        assert_eq!(storage.get("ws/a").await.unwrap(), Bytes::from_static(b"data"));
+        storage.delete("ws/a").await.unwrap();
+        assert!(matches!(storage.get("ws/a").await, Err(StorageError::NotFound)));
+        storage.delete("ws/a").await.unwrap();
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
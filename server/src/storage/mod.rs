@@ -0,0 +1,62 @@
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+
+mod local;
+#[cfg(feature = "s3")]
+mod s3;
+
+pub use local::LocalStorage;
+#[cfg(feature = "s3")]
+pub use s3::S3Storage;
+
+#[derive(Debug)]
+pub enum StorageError {
+    NotFound,
+    Backend(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::NotFound => write!(f, "object not found"),
+            StorageError::Backend(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Yüklenen dosyaların saklandığı yer. Anahtarlar sunucu tarafından üretilir
+/// (`<workspace_id>/<attachment_id>`), kullanıcıdan gelen dosya adı kullanılmaz.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), StorageError>;
+    async fn get(&self, key: &str) -> Result<Bytes, StorageError>;
+    /// Olmayan bir anahtarı silmek hata değildir.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+}
+
+pub type SharedStorage = Arc<dyn Storage>;
+
+/// `STORAGE_BACKEND` ortam değişkenine göre depolamayı oluşturur: `local` (varsayılan,
+/// `STORAGE_DIR` klasörü) ya da `s3` (sunucu `s3` özelliğiyle derlenmiş olmalı).
+pub fn from_env() -> SharedStorage {
+    match std::env::var("STORAGE_BACKEND").as_deref() {
+        Ok("s3") => s3_from_env(),
+        Ok("local") | Err(_) => Arc::new(LocalStorage::new(
+            std::env::var("STORAGE_DIR").unwrap_or_else(|_| "uploads".to_string()),
+        )),
+        Ok(other) => panic!("unknown STORAGE_BACKEND: {}", other),
+    }
+}
+
+#[cfg(feature = "s3")]
+fn s3_from_env() -> SharedStorage {
+    Arc::new(S3Storage::from_env())
+}
+
+#[cfg(not(feature = "s3"))]
+fn s3_from_env() -> SharedStorage {
+    panic!("STORAGE_BACKEND=s3 requires the server to be built with the `s3` feature")
+}
@@ -0,0 +1,215 @@
+use std::net::{IpAddr, SocketAddr};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+
+use crate::config;
+
+pub const SIGNATURE_HEADER: &str = "X-Axelte-Signature";
+pub const EVENT_HEADER: &str = "X-Axelte-Event";
+pub const DELIVERY_HEADER: &str = "X-Axelte-Delivery";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEvent {
+    PageCreated,
+    PageUpdated,
+    PageRenamed,
+    PageDeleted,
+    MemberAdded,
+    MemberRemoved,
+    MemberRoleChanged,
+    /// Sadece test isteğiyle gönderilir, filtreden bağımsızdır
+    Ping,
+}
+
+impl WebhookEvent {
+    pub const ALL: [WebhookEvent; 7] = [
+        WebhookEvent::PageCreated,
+        WebhookEvent::PageUpdated,
+        WebhookEvent::PageRenamed,
+        WebhookEvent::PageDeleted,
+        WebhookEvent::MemberAdded,
+        WebhookEvent::MemberRemoved,
+        WebhookEvent::MemberRoleChanged,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::PageCreated => "page.created",
+            WebhookEvent::PageUpdated => "page.updated",
+            WebhookEvent::PageRenamed => "page.renamed",
+            WebhookEvent::PageDeleted => "page.deleted",
+            WebhookEvent::MemberAdded => "member.added",
+            WebhookEvent::MemberRemoved => "member.removed",
+            WebhookEvent::MemberRoleChanged => "member.role_changed",
+            WebhookEvent::Ping => "ping",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<WebhookEvent> {
+        WebhookEvent::ALL.into_iter().find(|e| e.as_str() == name)
+    }
+}
+
+/// Webhook'un olay filtresi; boş liste bütün olaylara abone olmak demektir.
+pub fn subscribed(events: &[String], event: WebhookEvent) -> bool {
+    event == WebhookEvent::Ping || events.is_empty() || events.iter().any(|e| e == event.as_str())
+}
+
+/// Gövdenin HMAC-SHA256 imzası, `sha256=<hex>` biçiminde.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Webhook adresinin teslim edileceği yer: host ve çözümlenen adres.
+#[derive(Debug, Clone)]
+pub struct Destination {
+    pub url: reqwest::Url,
+    pub host: String,
+    pub addr: SocketAddr,
+}
+
+/// Adresi çözümler ve sadece genel internetteki bir hedefe izin verir. Loopback, özel ağ,
+/// link-local (bulut metadata servisleri dahil) ve benzeri adresler reddedilir; aksi halde
+/// workspace sahipleri sunucunun iç ağına istek attırabilir. Hem abonelikte hem her
+/// teslimatta çağrılır ki DNS sonradan iç bir adrese yönlendirilemesin.
+pub async fn check_destination(url: &str) -> Result<Destination, String> {
+    let url = reqwest::Url::parse(url)
+        .ok()
+        .filter(|u| u.scheme() == "http" || u.scheme() == "https")
+        .ok_or_else(|| "Webhook URL must be an absolute http(s) URL".to_string())?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| "Webhook URL must have a host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Could not resolve webhook host '{}': {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("Could not resolve webhook host '{}'", host));
+    }
+    if !config::webhook_allow_private_targets() {
+        if let Some(addr) = addrs.iter().find(|a| !is_public(a.ip())) {
+            return Err(format!(
+                "Webhook URL resolves to a non-public address ({})",
+                addr.ip()
+            ));
+        }
+    }
+
+    Ok(Destination {
+        addr: addrs[0],
+        host,
+        url,
+    })
+}
+
+/// Genel internette yönlendirilebilen bir adres mi.
+pub fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                // 100.64.0.0/10 (CGNAT) ve 198.18.0.0/15 (benchmark)
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (18..20).contains(&b))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public(IpAddr::V4(v4)),
+            None => {
+                !(ip.is_unspecified()
+                    || ip.is_loopback()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Olayı workspace'in ilgili webhook'ları için teslimat kuyruğuna ekler.
+/// Gönderimi `workers::webhook_delivery` yapar; buradaki hatalar sadece loglanır.
+pub async fn dispatch(pool: &MySqlPool, workspace_id: &str, event: WebhookEvent, data: Value) {
+    let hooks = match sqlx::query!(
+        r#"SELECT id, events as "events: sqlx::types::Json<Vec<String>>" FROM webhooks WHERE workspace_id = ? AND active = TRUE"#,
+        workspace_id
+    )
+    .fetch_all(pool)
+    .await
+    {
+        Ok(hooks) => hooks,
+        Err(e) => {
+            tracing::error!(%workspace_id, error = %e, "failed to load webhooks");
+            return;
+        }
+    };
+
+    for hook in hooks {
+        if subscribed(&hook.events, event) {
+            enqueue(pool, &hook.id, workspace_id, event, &data).await;
+        }
+    }
+}
+
+/// Tek bir webhook için teslimat oluşturur ve teslimat id'sini döndürür.
+pub async fn enqueue(
+    pool: &MySqlPool,
+    webhook_id: &str,
+    workspace_id: &str,
+    event: WebhookEvent,
+    data: &Value,
+) -> Option<String> {
+    let delivery_id = Uuid::new_v4().to_string();
+    // Gövde bir kez üretilir ki yeniden denemelerde imza aynı kalsın
+    let payload = json!({
+        "id": delivery_id,
+        "event": event.as_str(),
+        "workspace_id": workspace_id,
+        "created_at": Utc::now(),
+        "data": data,
+    })
+    .to_string();
+
+    let result = sqlx::query!(
+        "INSERT INTO webhook_deliveries (id, webhook_id, event, payload) VALUES (?, ?, ?, ?)",
+        delivery_id,
+        webhook_id,
+        event.as_str(),
+        payload
+    )
+    .execute(pool)
+    .await;
+
+    match result {
+        Ok(_) => Some(delivery_id),
+        Err(e) => {
+            tracing::error!(
+                event = event.as_str(),
+                %webhook_id,
+                error = %e,
+                "failed to enqueue webhook delivery"
+            );
+            None
+        }
+    }
+}
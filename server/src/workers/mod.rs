@@ -0,0 +1,2 @@
+pub mod trash_purge;
+pub mod webhook_delivery;
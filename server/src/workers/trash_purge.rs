@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use sqlx::{MySqlConnection, MySqlPool};
+
+use crate::handlers::page_tree::subtree_ids;
+use crate::storage::SharedStorage;
+
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Saklama süresi dolan çöp kutusu sayfalarını saatte bir kalıcı olarak siler.
+pub fn spawn(pool: MySqlPool, storage: SharedStorage, retention_days: u32) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_expired(&pool, retention_days).await {
+                tracing::error!(error = %e, "trash purge failed");
+            }
+            // Önceki turlarda silinemeyen dosyalar da burada yeniden denenir
+            if let Err(e) = purge_orphaned_attachments(&pool, &storage).await {
+                tracing::error!(error = %e, "attachment cleanup failed");
+            }
+        }
+    });
+}
+
+/// Saklama süresi dolan sayfaları alt ağaçlarıyla birlikte siler. Her kök ayrı bir işlemde
+/// silinir; biri başarısız olursa diğerleri yine silinir ve o kök bir sonraki turda denenir.
+pub async fn purge_expired(pool: &MySqlPool, retention_days: u32) -> Result<u64, sqlx::Error> {
+    // Ebeveyni de süresi dolmuş sayfalar, ebeveynin alt ağacıyla birlikte silinir
+    let roots = sqlx::query!(
+        "
+        SELECT p.id
+        FROM pages p
+        LEFT JOIN pages parent ON parent.id = p.parent_id
+            AND parent.deleted_at IS NOT NULL AND parent.deleted_at < NOW() - INTERVAL ? DAY
+        WHERE p.deleted_at IS NOT NULL AND p.deleted_at < NOW() - INTERVAL ? DAY
+            AND parent.id IS NULL
+        ",
+        retention_days,
+        retention_days
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut purged = 0;
+    for root in roots {
+        let mut tx = pool.begin().await?;
+        let result = match delete_subtree(&mut tx, &root.id).await {
+            Ok(page_ids) => tx.commit().await.map(|_| page_ids.len() as u64),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(count) => purged += count,
+            Err(e) => tracing::error!(page_id = %root.id, error = %e, "failed to purge page"),
+        }
+    }
+    Ok(purged)
+}
+
+/// Sayfayı ve bütün alt sayfalarını en derindekinden başlayarak siler. InnoDB 15 seviyeden
+/// derin `ON DELETE CASCADE` zincirlerini reddettiği için alt sayfalar tek tek silinir.
+/// Silinen sayfaların id'lerini döndürür.
+pub async fn delete_subtree(
+    conn: &mut MySqlConnection,
+    root_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let page_ids = subtree_ids(&mut *conn, root_id, true).await?;
+    for page_id in page_ids.iter().rev() {
+        sqlx::query!("DELETE FROM pages WHERE id = ?", page_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(page_ids)
+}
+
+/// Sayfası kalıcı olarak silinmiş (page_id NULL) eklentileri depolamadan ve tablodan siler.
+/// Depolamadan silinemeyen dosyanın kaydı bir sonraki tur için bırakılır.
+pub async fn purge_orphaned_attachments(
+    pool: &MySqlPool,
+    storage: &SharedStorage,
+) -> Result<u64, sqlx::Error> {
+    let orphans = sqlx::query!("SELECT id, storage_key FROM attachments WHERE page_id IS NULL")
+        .fetch_all(pool)
+        .await?;
+
+    let mut removed = 0;
+    for orphan in orphans {
+        if let Err(e) = storage.delete(&orphan.storage_key).await {
+            tracing::warn!(attachment_id = %orphan.id, error = %e, "failed to delete attachment");
+            continue;
+        }
+        sqlx::query!("DELETE FROM attachments WHERE id = ?", orphan.id)
+            .execute(pool)
+            .await?;
+        removed += 1;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use sqlx::MySqlPool;
+
+use crate::webhooks::{check_destination, sign, Destination, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const BATCH_SIZE: u32 = 20;
+/// Bu kadar denemeden sonra teslimat `failed` olarak bırakılır
+pub const MAX_ATTEMPTS: i32 = 6;
+const BASE_BACKOFF_SECS: u64 = 30;
+
+/// Bekleyen webhook teslimatlarını birkaç saniyede bir gönderir.
+pub fn spawn(pool: MySqlPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = deliver_due(&pool).await {
+                tracing::error!(error = %e, "webhook delivery failed");
+            }
+        }
+    });
+}
+
+/// n. başarısız denemeden sonraki bekleme: 30s, 1dk, 2dk, 4dk, ...
+pub fn backoff(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 10) as u32;
+    Duration::from_secs(BASE_BACKOFF_SECS * 2u64.pow(exponent))
+}
+
+/// Bir denemeden sonra teslimatın yeni durumu.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NextState {
+    Delivered,
+    /// `pending` kalır, bu kadar sonra yeniden denenir
+    Retry(Duration),
+    /// `MAX_ATTEMPTS` doldu
+    Failed,
+}
+
+/// `attempts`: bu deneme dahil yapılan deneme sayısı.
+pub fn next_state(attempts: i32, error: Option<&str>) -> NextState {
+    match error {
+        None => NextState::Delivered,
+        Some(_) if attempts >= MAX_ATTEMPTS => NextState::Failed,
+        Some(_) => NextState::Retry(backoff(attempts)),
+    }
+}
+
+/// Teslimat adresine sabitlenmiş istemci. Adres `check_destination`'da denetlenen IP'ye
+/// çözümlenir ve yönlendirmeler izlenmez; iç ağa ne DNS ne de yönlendirmeyle ulaşılabilir.
+fn client_for(destination: &Destination) -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(&destination.host, destination.addr)
+        .build()
+}
+
+/// İmzalı isteği gönderir; yanıtın durum kodunu ve başarısızsa hatayı döner.
+async fn post(
+    client: &reqwest::Client,
+    url: reqwest::Url,
+    secret: &str,
+    event: &str,
+    delivery_id: &str,
+    payload: &str,
+) -> (Option<u16>, Option<String>) {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event)
+        .header(DELIVERY_HEADER, delivery_id)
+        .header(SIGNATURE_HEADER, sign(secret, payload.as_bytes()))
+        .body(payload.to_string())
+        .send()
+        .await;
+
+    match response {
+        Ok(r) if r.status().is_success() => (Some(r.status().as_u16()), None),
+        Ok(r) => (Some(r.status().as_u16()), Some(format!("HTTP {}", r.status()))),
+        Err(e) => (None, Some(e.to_string())),
+    }
+}
+
+async fn deliver_due(pool: &MySqlPool) -> Result<(), sqlx::Error> {
+    let due = sqlx::query!(
+        "
+        SELECT d.id, d.event, d.payload, d.attempts, w.url, w.secret
+        FROM webhook_deliveries d
+        INNER JOIN webhooks w ON w.id = d.webhook_id
+        WHERE d.status = 'pending' AND d.next_attempt_at <= NOW() AND w.active = TRUE
+        ORDER BY d.next_attempt_at
+        LIMIT ?
+        ",
+        BATCH_SIZE
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for delivery in due {
+        // Adres her teslimatta yeniden denetlenir (abonelikten sonra DNS değişmiş olabilir)
+        let (status_code, error) = match check_destination(&delivery.url).await {
+            Ok(destination) => match client_for(&destination) {
+                Ok(client) => {
+                    post(
+                        &client,
+                        destination.url,
+                        &delivery.secret,
+                        &delivery.event,
+                        &delivery.id,
+                        &delivery.payload,
+                    )
+                    .await
+                }
+                Err(e) => (None, Some(e.to_string())),
+            },
+            Err(e) => (None, Some(e)),
+        };
+
+        let attempts = delivery.attempts + 1;
+        match next_state(attempts, error.as_deref()) {
+            NextState::Delivered => {
+                sqlx::query!(
+                    "
+                    UPDATE webhook_deliveries
+                    SET status = 'success', attempts = ?, last_status_code = ?, last_error = NULL, delivered_at = NOW()
+                    WHERE id = ?
+                    ",
+                    attempts,
+                    status_code,
+                    delivery.id
+                )
+                .execute(pool)
+                .await?;
+            }
+            next => {
+                let (status, retry_in) = match next {
+                    NextState::Retry(after) => ("pending", after),
+                    _ => ("failed", backoff(attempts)),
+                };
+                sqlx::query!(
+                    "
+                    UPDATE webhook_deliveries
+                    SET status = ?, attempts = ?, last_status_code = ?, last_error = ?,
+                        next_attempt_at = NOW() + INTERVAL ? SECOND
+                    WHERE id = ?
+                    ",
+                    status,
+                    attempts,
+                    status_code,
+                    error,
+                    retry_in.as_secs(),
+                    delivery.id
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        http::{HeaderMap, StatusCode},
+        response::Redirect,
+        routing::post as post_route,
+        Router,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Received {
+        signature: Option<String>,
+        event: Option<String>,
+        body: String,
+    }
+
+    /// Her isteği kaydedip 500 dönen yerel alıcı; adresini döner.
+    async fn receiver(received: Arc<Mutex<Vec<Received>>>) -> std::net::SocketAddr {
+        let app = Router::new()
+            .route(
+                "/hook",
+                post_route(move |headers: HeaderMap, body: String| {
+                    let received = received.clone();
+                    async move {
+                        let header = |name: &str| {
+                            headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+                        };
+                        received.lock().unwrap().push(Received {
+                            signature: header(SIGNATURE_HEADER),
+                            event: header(EVENT_HEADER),
+                            body,
+                        });
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            )
+            .route("/redirect", post_route(|| async { Redirect::temporary("/hook") }));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        addr
+    }
+
+    fn local_client(addr: std::net::SocketAddr) -> reqwest::Client {
+        client_for(&Destination {
+            url: format!("http://{}/", addr).parse().unwrap(),
+            host: addr.ip().to_string(),
+            addr,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signs_payload_and_reschedules_failed_delivery() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let addr = receiver(received.clone()).await;
+        let payload = r#"{"event":"page.created","data":{}}"#;
+
+        let url = format!("http://{}/hook", addr).parse().unwrap();
+        let (status, error) =
+            post(&local_client(addr), url, "s3cret", "page.created", "d1", payload).await;
+
+        assert_eq!(status, Some(500));
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].body, payload);
+        assert_eq!(received[0].event.as_deref(), Some("page.created"));
+        assert_eq!(received[0].signature, Some(sign("s3cret", payload.as_bytes())));
+        assert!(received[0].signature.as_deref().unwrap().starts_with("sha256="));
+
+        assert_eq!(next_state(1, error.as_deref()), NextState::Retry(backoff(1)));
+        assert_eq!(next_state(MAX_ATTEMPTS, error.as_deref()), NextState::Failed);
+        assert_eq!(next_state(1, None), NextState::Delivered);
+    }
+
+    #[tokio::test]
+    async fn does_not_follow_redirects() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let addr = receiver(received.clone()).await;
+
+        let url = format!("http://{}/redirect", addr).parse().unwrap();
+        let (status, error) = post(&local_client(addr), url, "s3cret", "ping", "d2", "{}").await;
+
+        assert_eq!(status, Some(307));
+        assert!(error.is_some());
+        assert!(received.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_private_destinations() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://localhost/hook",
+            "http://10.0.0.5/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://[::ffff:192.168.1.1]/hook",
+            "ftp://example.com/hook",
+        ] {
+            assert!(check_destination(url).await.is_err(), "{} should be rejected", url);
+        }
+    }
+}